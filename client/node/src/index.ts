@@ -12,9 +12,53 @@ enum Endpoints {
   ISSUE_LIST = "/issue/list",
   AUTHOR_COMMITS = "/author/-/commits",
   AUTHORS_LIST = "/author/list",
+  AUTHOR_FILES = "/author/-/files",
+  AUTHOR_ISSUES = "/author/-/issues",
+  FILE_AUTHORS = "/file/-/authors",
+  ISSUE_AUTHORS = "/issue/-/authors",
   COMMIT_AUTHORS = "/commit/-/authors",
+  FILE_SYMBOLS = "/file/-/symbols",
+  SYMBOL_COMMITS = "/symbol/-/commits",
+  SYMBOL_ISSUES = "/symbol/-/issues",
+  SYMBOLS_LIST = "/symbol/list",
+  FILE_RANGE_COMMITS = "/file/-/range/commits",
+  FILE_RANGE_ISSUES = "/file/-/range/issues",
+  FILE_RANGE_AUTHORS = "/file/-/range/authors",
+  FILE_OWNERS = "/file/-/owners",
+  COMMIT_INFO = "/commit/-/info",
+  FILE_KINDS = "/file/-/kinds",
+  FILE_KIND_COMMITS = "/file/-/kind/commits",
+  FILE_SCOPES = "/file/-/scopes",
+  SCOPE_FILES = "/scope/-/files",
+  SCOPE_COMMITS = "/scope/-/commits",
+  SCOPES_LIST = "/scope/list",
+  COMMIT_PERSONS = "/commit/-/persons",
+  FILE_REVIEWERS = "/file/-/reviewers",
+  PERSON_COMMITS = "/person/-/commits",
+  PERSON_LOAD = "/person/load",
+  PERSONS_LIST = "/person/list",
+  WORKTREE_IMPACT = "/worktree/impact",
+  FILE_COUPLED = "/file/-/coupled",
+  COUPLING = "/coupling",
+  TRAVERSE = "/traverse",
+  PATH = "/path",
+  QUERY = "/query",
+  FILE_HOT = "/file/-/hot",
+  HOT_FILES = "/file/hot",
+  FILE_BUS = "/file/-/bus",
+  DIR_BUS = "/dir/-/bus",
+  BUS = "/bus",
+  FILE_EXPERTS = "/file/-/experts",
+  DIR_EXPERTS = "/dir/-/experts",
+  ISSUE_EXPERTS = "/issue/-/experts",
+  COMMUNITIES = "/communities",
+  CENTRALITY = "/centrality",
+  SUBGRAPH = "/subgraph",
+  SUBGRAPH_DOT = "/subgraph/dot",
 }
 
+export type NodeType = "file" | "commit" | "issue" | "author" | "symbol" | "scope" | "person";
+
 export class CupidoClient {
   private apiBaseUrl: string;
 
@@ -32,6 +76,16 @@ export class CupidoClient {
     }
   }
 
+  private async postData(endpoint: string, body: any): Promise<any> {
+    try {
+      const response: AxiosResponse<any> = await axios.post<any>(`${this.apiBaseUrl}${endpoint}`, body);
+      return response.data;
+    } catch (error: any) {
+      console.error('Error:', error.message);
+      throw error;
+    }
+  }
+
   async api_root(): Promise<any> {
     return this.getData(Endpoints.ROOT);
   }
@@ -80,6 +134,26 @@ export class CupidoClient {
     return this.getData(endpoint);
   }
 
+  async api_authorRelatedFiles(author: string): Promise<any[]> {
+    const endpoint = Endpoints.AUTHOR_FILES + `?author=${encodeURIComponent(author)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_authorRelatedIssues(author: string): Promise<any[]> {
+    const endpoint = Endpoints.AUTHOR_ISSUES + `?author=${encodeURIComponent(author)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_fileRelatedAuthors(file: string): Promise<any[]> {
+    const endpoint = Endpoints.FILE_AUTHORS + `?file=${encodeURIComponent(file)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_issueRelatedAuthors(issue: string): Promise<any[]> {
+    const endpoint = Endpoints.ISSUE_AUTHORS + `?issue=${encodeURIComponent(issue)}`;
+    return this.getData(endpoint);
+  }
+
   async api_authorsList(): Promise<string[]> {
     const endpoint = Endpoints.AUTHORS_LIST;
     return this.getData(endpoint);
@@ -89,4 +163,206 @@ export class CupidoClient {
     const endpoint = Endpoints.COMMIT_AUTHORS + `?commit=${encodeURIComponent(commit)}`;
     return this.getData(endpoint);
   }
+
+  async api_fileRelatedSymbols(file: string): Promise<string[]> {
+    const endpoint = Endpoints.FILE_SYMBOLS + `?file=${encodeURIComponent(file)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_symbolRelatedCommits(symbol: string): Promise<string[]> {
+    const endpoint = Endpoints.SYMBOL_COMMITS + `?symbol=${encodeURIComponent(symbol)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_symbolRelatedIssues(symbol: string): Promise<string[]> {
+    const endpoint = Endpoints.SYMBOL_ISSUES + `?symbol=${encodeURIComponent(symbol)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_symbolsList(): Promise<string[]> {
+    const endpoint = Endpoints.SYMBOLS_LIST;
+    return this.getData(endpoint);
+  }
+
+  async api_fileRangeCommits(file: string, start: number, end: number): Promise<string[]> {
+    const endpoint = Endpoints.FILE_RANGE_COMMITS + `?file=${encodeURIComponent(file)}&start=${start}&end=${end}`;
+    return this.getData(endpoint);
+  }
+
+  async api_fileRangeIssues(file: string, start: number, end: number): Promise<string[]> {
+    const endpoint = Endpoints.FILE_RANGE_ISSUES + `?file=${encodeURIComponent(file)}&start=${start}&end=${end}`;
+    return this.getData(endpoint);
+  }
+
+  async api_fileRangeAuthors(file: string, start: number, end: number): Promise<string[]> {
+    const endpoint = Endpoints.FILE_RANGE_AUTHORS + `?file=${encodeURIComponent(file)}&start=${start}&end=${end}`;
+    return this.getData(endpoint);
+  }
+
+  async api_fileOwners(file: string): Promise<any> {
+    const endpoint = Endpoints.FILE_OWNERS + `?file=${encodeURIComponent(file)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_commitInfo(commit: string): Promise<any> {
+    const endpoint = Endpoints.COMMIT_INFO + `?commit=${encodeURIComponent(commit)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_fileCommitKinds(file: string): Promise<Record<string, number>> {
+    const endpoint = Endpoints.FILE_KINDS + `?file=${encodeURIComponent(file)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_fileRelatedCommitsByKind(file: string, kind: string): Promise<string[]> {
+    const endpoint = Endpoints.FILE_KIND_COMMITS + `?file=${encodeURIComponent(file)}&kind=${encodeURIComponent(kind)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_fileRelatedScopes(file: string): Promise<string[]> {
+    const endpoint = Endpoints.FILE_SCOPES + `?file=${encodeURIComponent(file)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_scopeRelatedFiles(scope: string): Promise<string[]> {
+    const endpoint = Endpoints.SCOPE_FILES + `?scope=${encodeURIComponent(scope)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_scopeRelatedCommits(scope: string): Promise<string[]> {
+    const endpoint = Endpoints.SCOPE_COMMITS + `?scope=${encodeURIComponent(scope)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_scopesList(): Promise<string[]> {
+    const endpoint = Endpoints.SCOPES_LIST;
+    return this.getData(endpoint);
+  }
+
+  async api_commitRelatedPersons(commit: string): Promise<any[]> {
+    const endpoint = Endpoints.COMMIT_PERSONS + `?commit=${encodeURIComponent(commit)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_fileRelatedReviewers(file: string, role?: string): Promise<Record<string, number>> {
+    let endpoint = Endpoints.FILE_REVIEWERS + `?file=${encodeURIComponent(file)}`;
+    if (role) {
+      endpoint += `&role=${encodeURIComponent(role)}`;
+    }
+    return this.getData(endpoint);
+  }
+
+  async api_dirRelatedReviewers(dir: string, role?: string): Promise<Record<string, number>> {
+    let endpoint = Endpoints.FILE_REVIEWERS + `?dir=${encodeURIComponent(dir)}`;
+    if (role) {
+      endpoint += `&role=${encodeURIComponent(role)}`;
+    }
+    return this.getData(endpoint);
+  }
+
+  async api_personRelatedCommits(person: string): Promise<string[]> {
+    const endpoint = Endpoints.PERSON_COMMITS + `?person=${encodeURIComponent(person)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_reviewerLoad(role?: string): Promise<Record<string, number>> {
+    let endpoint: string = Endpoints.PERSON_LOAD;
+    if (role) {
+      endpoint += `?role=${encodeURIComponent(role)}`;
+    }
+    return this.getData(endpoint);
+  }
+
+  async api_personsList(): Promise<string[]> {
+    const endpoint = Endpoints.PERSONS_LIST;
+    return this.getData(endpoint);
+  }
+
+  async api_worktreeImpact(): Promise<any> {
+    return this.getData(Endpoints.WORKTREE_IMPACT);
+  }
+
+  async api_fileCoupledFiles(file: string, minSupport: number = 1): Promise<any[]> {
+    const endpoint = Endpoints.FILE_COUPLED + `?file=${encodeURIComponent(file)}&min_support=${minSupport}`;
+    return this.getData(endpoint);
+  }
+
+  async api_couplingReport(minSupport: number = 2): Promise<any[]> {
+    const endpoint = Endpoints.COUPLING + `?min_support=${minSupport}`;
+    return this.getData(endpoint);
+  }
+
+  async api_traverse(type: NodeType, name: string, path: NodeType[]): Promise<any[]> {
+    const endpoint = Endpoints.TRAVERSE + `?type=${type}&name=${encodeURIComponent(name)}&path=${path.join(",")}`;
+    return this.getData(endpoint);
+  }
+
+  async api_shortestPath(fromType: NodeType, from: string, toType: NodeType, to: string, maxHops: number = 4): Promise<any> {
+    const endpoint = Endpoints.PATH + `?from_type=${fromType}&from=${encodeURIComponent(from)}&to_type=${toType}&to=${encodeURIComponent(to)}&max_hops=${maxHops}`;
+    return this.getData(endpoint);
+  }
+
+  async api_query(query: string): Promise<any> {
+    return this.postData(Endpoints.QUERY, { query });
+  }
+
+  async api_fileHot(file: string): Promise<any> {
+    const endpoint = Endpoints.FILE_HOT + `?file=${encodeURIComponent(file)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_hotFiles(top: number = 10): Promise<any[]> {
+    const endpoint = Endpoints.HOT_FILES + `?top=${top}`;
+    return this.getData(endpoint);
+  }
+
+  async api_fileBusFactor(file: string, coverage: number = 0.5, inactiveMonths: number = 6): Promise<any> {
+    const endpoint = Endpoints.FILE_BUS + `?file=${encodeURIComponent(file)}&coverage=${coverage}&inactive_months=${inactiveMonths}`;
+    return this.getData(endpoint);
+  }
+
+  async api_dirBusFactor(dir: string, coverage: number = 0.5, inactiveMonths: number = 6): Promise<any> {
+    const endpoint = Endpoints.DIR_BUS + `?dir=${encodeURIComponent(dir)}&coverage=${coverage}&inactive_months=${inactiveMonths}`;
+    return this.getData(endpoint);
+  }
+
+  async api_busFactorReport(coverage: number = 0.5, inactiveMonths: number = 6, dirDepth: number = 1): Promise<any> {
+    const endpoint = Endpoints.BUS + `?coverage=${coverage}&inactive_months=${inactiveMonths}&dir_depth=${dirDepth}`;
+    return this.getData(endpoint);
+  }
+
+  async api_fileExperts(file: string): Promise<any[]> {
+    const endpoint = Endpoints.FILE_EXPERTS + `?file=${encodeURIComponent(file)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_dirExperts(dir: string): Promise<any[]> {
+    const endpoint = Endpoints.DIR_EXPERTS + `?dir=${encodeURIComponent(dir)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_issueExperts(issue: string): Promise<any[]> {
+    const endpoint = Endpoints.ISSUE_EXPERTS + `?issue=${encodeURIComponent(issue)}`;
+    return this.getData(endpoint);
+  }
+
+  async api_communities(via: NodeType = "commit", minWeight: number = 1, minSize: number = 2): Promise<any[]> {
+    const endpoint = Endpoints.COMMUNITIES + `?via=${via}&min_weight=${minWeight}&min_size=${minSize}`;
+    return this.getData(endpoint);
+  }
+
+  async api_centrality(metric: "pagerank" | "betweenness" | "degree" = "pagerank", type: NodeType = "file", within: NodeType[] = ["file", "issue"], top: number = 10): Promise<any[]> {
+    const endpoint = Endpoints.CENTRALITY + `?metric=${metric}&kind=${type}&within=${within.join(",")}&top=${top}`;
+    return this.getData(endpoint);
+  }
+
+  async api_subgraph(type: NodeType, name: string, hops: number = 2, kinds: NodeType[] = [], maxNodes: number = 200): Promise<any> {
+    const endpoint = Endpoints.SUBGRAPH + `?type=${type}&name=${encodeURIComponent(name)}&hops=${hops}&kinds=${kinds.join(",")}&max_nodes=${maxNodes}`;
+    return this.getData(endpoint);
+  }
+
+  async api_subgraphDot(type: NodeType, name: string, hops: number = 2, kinds: NodeType[] = [], maxNodes: number = 200): Promise<string> {
+    const endpoint = Endpoints.SUBGRAPH_DOT + `?type=${type}&name=${encodeURIComponent(name)}&hops=${hops}&kinds=${kinds.join(",")}&max_nodes=${maxNodes}`;
+    return this.getData(endpoint);
+  }
 }
@@ -0,0 +1,82 @@
+use crate::collector::config::Config;
+use crate::collector::notes::{commit_issues, read_notes};
+use crate::relation::graph::RelationGraph;
+use git2::{BlameOptions, Commit, Oid, Repository};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+
+/// Blame every file at HEAD, and store the surviving lines per author and per issue.
+/// Returns false if stopped early by cancellation or time budget.
+pub(crate) fn blame_pass(
+    repo: &Repository,
+    head: &Commit,
+    re: &Regex,
+    conf: &Config,
+    start_time: Instant,
+    graph: &mut RelationGraph,
+) -> bool {
+    let head_tree = head.tree().expect("Failed to get HEAD tree");
+    // commit id -> issues, blame hunks share commits a lot
+    let mut issue_cache: HashMap<Oid, Vec<String>> = HashMap::new();
+
+    for file in graph.files() {
+        if conf.cancel_token.is_cancelled() {
+            return false;
+        }
+        if let Some(budget) = conf.time_budget {
+            if start_time.elapsed() > budget {
+                return false;
+            }
+        }
+
+        // removed files have no owner
+        if head_tree.get_path(Path::new(&file)).is_err() {
+            continue;
+        }
+
+        let mut opts = BlameOptions::new();
+        opts.newest_commit(head.id());
+        let blame = match repo.blame_file(Path::new(&file), Some(&mut opts)) {
+            Ok(blame) => blame,
+            Err(_) => {
+                eprintln!("Failed to blame {}", file);
+                continue;
+            }
+        };
+
+        let mut total_lines = 0;
+        let mut author_lines: HashMap<String, usize> = HashMap::new();
+        let mut issue_lines: HashMap<String, usize> = HashMap::new();
+        for hunk in blame.iter() {
+            let lines = hunk.lines_in_hunk();
+            total_lines += lines;
+            *author_lines
+                .entry(hunk.final_signature().to_string())
+                .or_insert(0) += lines;
+
+            let commit_id = hunk.final_commit_id();
+            // each issue counts the lines once, however often it is mentioned
+            let issues = issue_cache.entry(commit_id).or_insert_with(|| {
+                let message = match repo.find_commit(commit_id) {
+                    Ok(commit) => commit.message().unwrap_or_default().to_string(),
+                    Err(_) => String::new(),
+                };
+                let notes = read_notes(repo, commit_id, &conf.notes_refs);
+                return commit_issues(re, &message, &notes);
+            });
+            for issue in issues.iter() {
+                *issue_lines.entry(issue.clone()).or_insert(0) += lines;
+            }
+        }
+
+        graph.set_file_owners(
+            &file,
+            total_lines,
+            author_lines.into_iter().collect(),
+            issue_lines.into_iter().collect(),
+        );
+    }
+    return true;
+}
@@ -1,10 +1,21 @@
 use crate::collector::native::NativeCollector;
+use crate::collector::observer::WalkObserver;
 use crate::relation::graph::RelationGraph;
 use serde_derive::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 pub struct CommitResult {
     pub files: Vec<String>,
     pub issues: Vec<String>,
+    /// (file, symbol) pairs
+    pub symbols: Vec<(String, String)>,
+    /// (file, surviving line ranges at HEAD) pairs
+    pub line_ranges: Vec<(String, Vec<(u32, u32)>)>,
+    pub notes: Vec<String>,
+    /// (file, lines added + deleted) pairs
+    pub churn: Vec<(String, usize)>,
 }
 
 impl CommitResult {
@@ -12,10 +23,34 @@ impl CommitResult {
         CommitResult {
             files: Vec::new(),
             issues: Vec::new(),
+            symbols: Vec::new(),
+            line_ranges: Vec::new(),
+            notes: Vec::new(),
+            churn: Vec::new(),
         }
     }
 }
 
+/// shared flag for stopping a running walk, e.g. from another thread
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    flag: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        return CancelToken::default();
+    }
+
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        return self.flag.load(Ordering::SeqCst);
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Config {
     pub repo_path: String,
@@ -26,6 +61,28 @@ pub struct Config {
     pub progress: bool,
     pub commit_exclude_regex: Option<String>,
     pub author_exclude_regex: Option<String>,
+    /// stop walking once exceeded, leaving a partial graph
+    pub time_budget: Option<Duration>,
+    #[serde(skip)]
+    pub cancel_token: CancelToken,
+    /// create function level symbol nodes from diff hunks
+    pub symbols: bool,
+    /// store hunk line ranges (mapped to HEAD) for range queries.
+    /// Needs one linear history, so it is turned off with `multi_parents`.
+    /// Renamed files start over at the rename.
+    pub line_ranges: bool,
+    /// blame files at HEAD for current ownership
+    pub blame: bool,
+    /// create scope nodes from conventional commits, linked to files
+    pub scope_nodes: bool,
+    /// trailer keys (e.g. `Reviewed-by`) creating person nodes
+    pub trailers: Vec<String>,
+    /// notes refs (e.g. `refs/notes/commits`) read as commit annotations
+    pub notes_refs: Vec<String>,
+    /// count changed lines per file and commit, for hotness scoring
+    pub churn: bool,
+    /// start walking from this rev (e.g. a tag) instead of HEAD
+    pub rev: Option<String>,
     // todo: node types should be optional
 }
 
@@ -40,6 +97,16 @@ impl Clone for Config {
             progress: self.progress,
             commit_exclude_regex: self.commit_exclude_regex.clone(),
             author_exclude_regex: self.author_exclude_regex.clone(),
+            time_budget: self.time_budget,
+            cancel_token: self.cancel_token.clone(),
+            symbols: self.symbols,
+            line_ranges: self.line_ranges,
+            blame: self.blame,
+            scope_nodes: self.scope_nodes,
+            trailers: self.trailers.clone(),
+            notes_refs: self.notes_refs.clone(),
+            churn: self.churn,
+            rev: self.rev.clone(),
         }
     }
 }
@@ -55,12 +122,28 @@ impl Config {
             progress: false,
             commit_exclude_regex: None,
             author_exclude_regex: None,
+            time_budget: None,
+            cancel_token: CancelToken::new(),
+            symbols: false,
+            line_ranges: false,
+            blame: false,
+            scope_nodes: false,
+            trailers: Vec::default(),
+            notes_refs: Vec::default(),
+            churn: false,
+            rev: None,
         }
     }
 }
 
 pub trait Collect {
     fn walk(&self, conf: Config) -> RelationGraph;
+
+    /// walk with a custom observer receiving per-commit events.
+    /// Collectors without events just walk, the observer hears nothing.
+    fn walk_with_observer(&self, conf: Config, _observer: &mut dyn WalkObserver) -> RelationGraph {
+        return self.walk(conf);
+    }
 }
 
 pub fn get_collector() -> impl Collect {
@@ -0,0 +1,29 @@
+use regex::Regex;
+
+lazy_static::lazy_static! {
+    // type(scope)!: description
+    static ref HEADER_REGEX: Regex =
+        Regex::new(r"^(?P<kind>[A-Za-z]+)(?:\((?P<scope>[^()\r\n]+)\))?(?P<breaking>!)?: ?\S").unwrap();
+    static ref BREAKING_FOOTER_REGEX: Regex =
+        Regex::new(r"(?m)^BREAKING[ -]CHANGE: ").unwrap();
+}
+
+/// https://www.conventionalcommits.org/
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ConventionalCommit {
+    pub(crate) kind: String,
+    pub(crate) scope: Option<String>,
+    pub(crate) breaking: bool,
+}
+
+pub(crate) fn parse_conventional(message: &str) -> Option<ConventionalCommit> {
+    let header = message.lines().next().unwrap_or_default();
+    let caps = HEADER_REGEX.captures(header)?;
+    return Some(ConventionalCommit {
+        kind: caps["kind"].to_lowercase(),
+        scope: caps
+            .name("scope")
+            .map(|scope| scope.as_str().trim().to_string()),
+        breaking: caps.name("breaking").is_some() || BREAKING_FOOTER_REGEX.is_match(message),
+    });
+}
@@ -0,0 +1,106 @@
+use git2::{Delta, Diff, DiffOptions, Repository, RevparseMode};
+
+pub(crate) fn diff_files(diff: &Diff) -> Vec<String> {
+    return diff
+        .deltas()
+        .filter_map(|delta| {
+            delta
+                .new_file()
+                .path()
+                .or(delta.old_file().path())
+                .map(|path| path.to_string_lossy().into_owned())
+        })
+        .collect();
+}
+
+/// files changed by a revision range like `base..head`, `base...head` (from merge base)
+/// or a single revision (against its first parent)
+pub fn range_changed_files(
+    repo_path: &str,
+    range: &str,
+    path_specs: &[String],
+) -> Result<Vec<String>, git2::Error> {
+    let repo = Repository::open(repo_path)?;
+    let spec = repo.revparse(range)?;
+
+    let (base, head) = if spec.mode().contains(RevparseMode::SINGLE) {
+        let head = spec
+            .from()
+            .ok_or(git2::Error::from_str("empty revision"))?
+            .peel_to_commit()?;
+        let base = head.parent(0).ok();
+        (base, head)
+    } else {
+        let from = spec
+            .from()
+            .ok_or(git2::Error::from_str("missing base revision"))?
+            .peel_to_commit()?;
+        let head = spec
+            .to()
+            .ok_or(git2::Error::from_str("missing head revision"))?
+            .peel_to_commit()?;
+        let base = if spec.mode().contains(RevparseMode::MERGE_BASE) {
+            repo.find_commit(repo.merge_base(from.id(), head.id())?)?
+        } else {
+            from
+        };
+        (Some(base), head)
+    };
+
+    let mut opts = DiffOptions::default();
+    for each in path_specs {
+        opts.pathspec(each);
+    }
+    opts.ignore_filemode(true);
+
+    let base_tree = match base {
+        Some(base) => Some(base.tree()?),
+        None => None,
+    };
+    let diff = repo.diff_tree_to_tree(base_tree.as_ref(), Some(&head.tree()?), Some(&mut opts))?;
+    return Ok(diff_files(&diff));
+}
+
+/// files changed by a patch in unified diff format, e.g. from `git diff`
+pub fn patch_changed_files(patch: &[u8]) -> Result<Vec<String>, git2::Error> {
+    let diff = Diff::from_buffer(patch)?;
+    return Ok(diff_files(&diff));
+}
+
+/// files added and deleted between the trees of two revisions (e.g. a tag and `HEAD`), sorted.
+/// Unlike `RelationGraph::graph_diff` this sees files deleted in between.
+pub fn rev_added_removed_files(
+    repo_path: &str,
+    from: &str,
+    to: &str,
+    path_specs: &[String],
+) -> Result<(Vec<String>, Vec<String>), git2::Error> {
+    let repo = Repository::open(repo_path)?;
+    let from_tree = repo.revparse_single(from)?.peel_to_commit()?.tree()?;
+    let to_tree = repo.revparse_single(to)?.peel_to_commit()?.tree()?;
+
+    let mut opts = DiffOptions::default();
+    for each in path_specs {
+        opts.pathspec(each);
+    }
+    let diff = repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), Some(&mut opts))?;
+
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    for delta in diff.deltas() {
+        match delta.status() {
+            Delta::Added => added.extend(delta.new_file().path()),
+            Delta::Deleted => removed.extend(delta.old_file().path()),
+            _ => {}
+        }
+    }
+    let sorted = |paths: Vec<&std::path::Path>| {
+        let mut files: Vec<String> = paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        return files;
+    };
+    return Ok((sorted(added), sorted(removed)));
+}
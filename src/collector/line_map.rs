@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+/// a diff hunk header: `@@ -old_start,old_lines +new_start,new_lines @@`
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Hunk {
+    pub(crate) old_start: u32,
+    pub(crate) old_lines: u32,
+    pub(crate) new_start: u32,
+    pub(crate) new_lines: u32,
+}
+
+/// Maps line ranges of older revisions forward to HEAD line numbers.
+/// Commits must be fed from newest to oldest, and hunks need zero context lines.
+#[derive(Default)]
+pub(crate) struct LineTracker {
+    // per file, hunks of each commit already seen (newest first)
+    transforms: HashMap<String, Vec<Vec<Hunk>>>,
+}
+
+impl LineTracker {
+    /// returns the lines added by this commit which survive at HEAD,
+    /// as inclusive (start, end) ranges
+    pub(crate) fn track(&mut self, file: &str, hunks: Vec<Hunk>) -> Vec<(u32, u32)> {
+        let mut ranges: Vec<(u32, u32)> = hunks
+            .iter()
+            .filter(|hunk| hunk.new_lines > 0)
+            .map(|hunk| (hunk.new_start, hunk.new_start + hunk.new_lines - 1))
+            .collect();
+
+        let transforms = self.transforms.entry(file.to_string()).or_default();
+        // from the oldest newer commit up to HEAD
+        for transform in transforms.iter().rev() {
+            if ranges.is_empty() {
+                break;
+            }
+            ranges = ranges
+                .iter()
+                .flat_map(|(start, end)| map_range(transform, *start, *end))
+                .collect();
+        }
+        transforms.push(hunks);
+        return ranges;
+    }
+}
+
+/// map an inclusive range from the old side of `hunks` to the new side,
+/// lines replaced or deleted by the hunks are dropped
+fn map_range(hunks: &[Hunk], start: u32, end: u32) -> Vec<(u32, u32)> {
+    let mut ret = Vec::new();
+    let mut cursor = start as i64;
+    let end = end as i64;
+    let mut offset: i64 = 0;
+
+    for hunk in hunks {
+        let old_start = hunk.old_start as i64;
+        let delta = hunk.new_lines as i64 - hunk.old_lines as i64;
+
+        if hunk.old_lines == 0 {
+            // pure insertion after `old_start`
+            if old_start >= end {
+                break;
+            }
+            if old_start >= cursor {
+                ret.push((cursor + offset, old_start + offset));
+                cursor = old_start + 1;
+            }
+            offset += delta;
+            continue;
+        }
+
+        let old_end = old_start + hunk.old_lines as i64 - 1;
+        if old_end < cursor {
+            offset += delta;
+            continue;
+        }
+        if old_start > end {
+            break;
+        }
+        if cursor < old_start {
+            ret.push((cursor + offset, old_start - 1 + offset));
+        }
+        cursor = old_end + 1;
+        offset += delta;
+        if cursor > end {
+            break;
+        }
+    }
+    if cursor <= end {
+        ret.push((cursor + offset, end + offset));
+    }
+
+    return ret
+        .into_iter()
+        .map(|(start, end)| (start as u32, end as u32))
+        .collect();
+}
@@ -1,2 +1,11 @@
+mod blame;
 pub mod config;
+mod conventional;
+pub mod diff;
+mod line_map;
 mod native;
+mod notes;
+pub mod observer;
+mod symbol;
+mod trailer;
+pub mod worktree;
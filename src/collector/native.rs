@@ -1,28 +1,40 @@
+use crate::collector::blame::blame_pass;
 use crate::collector::config::{Collect, CommitResult, Config};
+use crate::collector::conventional::parse_conventional;
+use crate::collector::line_map::{Hunk, LineTracker};
+use crate::collector::notes::{commit_issues, read_notes};
+use crate::collector::observer::{CommitEvent, NoopObserver, ProgressBarObserver, WalkObserver};
+use crate::collector::symbol::extract_symbols;
+use crate::collector::trailer::parse_trailers;
 use crate::relation::graph::RelationGraph;
-use git2::{Commit, DiffOptions, Repository};
-use indicatif::{ProgressBar, ProgressState, ProgressStyle};
+use git2::{Commit, DiffOptions, Patch, Repository, Revwalk};
 use regex::Regex;
-use std::fmt::Write;
+use std::time::Instant;
 
 pub struct NativeCollector {}
 
 impl Collect for NativeCollector {
-    fn walk(&self, mut conf: Config) -> RelationGraph {
+    fn walk(&self, conf: Config) -> RelationGraph {
+        if conf.progress {
+            let mut observer = ProgressBarObserver::new(conf.depth as u64);
+            return self.walk_with_observer(conf, &mut observer);
+        }
+        return self.walk_with_observer(conf, &mut NoopObserver {});
+    }
+
+    fn walk_with_observer(
+        &self,
+        mut conf: Config,
+        observer: &mut dyn WalkObserver,
+    ) -> RelationGraph {
         let repo_path = &conf.repo_path;
         let repo = Repository::open(repo_path).expect("Failed to open repository");
         conf.repo_path = absolute_path(repo_path);
-        return walk_dfs(conf, &repo);
+        return walk_dfs(conf, &repo, observer);
     }
 }
 
-fn walk_dfs(conf: Config, repo: &Repository) -> RelationGraph {
-    let head = repo
-        .head()
-        .expect("Failed to get HEAD ref")
-        .peel_to_commit()
-        .expect("Failed to peel HEAD to commit");
-
+fn create_revwalk<'repo>(conf: &Config, repo: &'repo Repository, head: &Commit) -> Revwalk<'repo> {
     let mut revwalk = repo.revwalk().expect("Failed to create revwalk");
     revwalk.push(head.id()).expect("Failed to push commit");
 
@@ -38,20 +50,75 @@ fn walk_dfs(conf: Config, repo: &Repository) -> RelationGraph {
             .simplify_first_parent()
             .expect("failed to set simplify_first_parent");
     }
+    return revwalk;
+}
+
+fn walk_dfs(mut conf: Config, repo: &Repository, observer: &mut dyn WalkObserver) -> RelationGraph {
+    // branches interleave in a multi parent walk, mapping hunks through them gives wrong lines
+    if conf.multi_parents && conf.line_ranges {
+        eprintln!("line ranges need a first parent walk, disabled");
+        conf.line_ranges = false;
+    }
+
+    let head = match &conf.rev {
+        Some(rev) => repo
+            .revparse_single(rev)
+            .expect("Failed to find rev")
+            .peel_to_commit()
+            .expect("Failed to peel rev to commit"),
+        None => repo
+            .head()
+            .expect("Failed to get HEAD ref")
+            .peel_to_commit()
+            .expect("Failed to peel HEAD to commit"),
+    };
+
+    let revwalk = create_revwalk(&conf, repo, &head);
 
     let mut counter = 0;
     let mut graph = RelationGraph::new();
     graph.conf = conf.clone();
 
     let issue_regex: Regex = Regex::new(&*conf.issue_regex).unwrap();
-    let commit_exclude_regex: Option<Regex> = conf.commit_exclude_regex.as_ref().map(|pattern| Regex::new(pattern).unwrap());
-    let author_exclude_regex: Option<Regex> = conf.author_exclude_regex
+    let commit_exclude_regex: Option<Regex> = conf
+        .commit_exclude_regex
         .as_ref()
         .map(|pattern| Regex::new(pattern).unwrap());
+    let author_exclude_regex: Option<Regex> = conf
+        .author_exclude_regex
+        .as_ref()
+        .map(|pattern| Regex::new(pattern).unwrap());
+    let mut tracker = LineTracker::default();
+
+    let start_time = Instant::now();
+    let stopped = |conf: &Config| {
+        return conf.cancel_token.is_cancelled()
+            || conf
+                .time_budget
+                .is_some_and(|budget| start_time.elapsed() > budget);
+    };
 
-    let pb = create_progress(conf.depth as u64);
+    // the real total is only knowable when the depth limit will not cut the walk,
+    // so never count past it
+    let mut total = None;
+    if observer.needs_total() {
+        let limit = (conf.depth as usize).saturating_add(1);
+        let count = create_revwalk(&conf, repo, &head)
+            .take(limit)
+            .take_while(|_| !stopped(&conf))
+            .count();
+        if count < limit && !stopped(&conf) {
+            total = Some(count as u64);
+        }
+    }
+    observer.on_start(total);
 
     for id in revwalk {
+        if stopped(&conf) {
+            graph.complete = false;
+            break;
+        }
+
         let commit_id = match id {
             Ok(commit_id) => commit_id,
             Err(_) => {
@@ -72,6 +139,11 @@ fn walk_dfs(conf: Config, repo: &Repository) -> RelationGraph {
         if commit_exclude_regex.is_some() {
             if let Some(exclude_regex) = &commit_exclude_regex {
                 if exclude_regex.is_match(commit.message().unwrap_or_default()) {
+                    if conf.line_ranges {
+                        // still changes lines of newer revisions
+                        process_commit(repo, &commit, &issue_regex, &conf, &mut tracker);
+                    }
+                    observer.on_skip(&commit_id.to_string());
                     continue;
                 }
             }
@@ -82,14 +154,19 @@ fn walk_dfs(conf: Config, repo: &Repository) -> RelationGraph {
         if author_exclude_regex.is_some() {
             if let Some(regex) = &author_exclude_regex {
                 if regex.is_match(author_str) {
+                    if conf.line_ranges {
+                        process_commit(repo, &commit, &issue_regex, &conf, &mut tracker);
+                    }
+                    observer.on_skip(&commit_id.to_string());
                     continue;
                 }
             }
         }
 
-        let commit_result = process_commit(&repo, &commit, &issue_regex, &conf);
+        let commit_result = process_commit(repo, &commit, &issue_regex, &conf, &mut tracker);
 
         if commit_result.files.is_empty() {
+            observer.on_skip(&commit_id.to_string());
             continue;
         }
 
@@ -105,6 +182,35 @@ fn walk_dfs(conf: Config, repo: &Repository) -> RelationGraph {
             graph.add_edge_file2commit(file, commit_id_str);
         }
 
+        if let Some(data) = graph.commit_data_mut(commit_id_str) {
+            data.time = Some(commit.time().seconds());
+        }
+
+        // conventional commit
+        if let Some(conventional) = parse_conventional(commit.message().unwrap_or_default()) {
+            if conf.scope_nodes {
+                if let Some(scope) = &conventional.scope {
+                    graph.add_scope_node(scope);
+                    graph.add_edge_scope2commit(scope, commit_id_str);
+                    for file in &commit_result.files {
+                        graph.add_edge_file2scope(file, scope);
+                    }
+                }
+            }
+            if let Some(data) = graph.commit_data_mut(commit_id_str) {
+                data.kind = Some(conventional.kind);
+                data.scope = conventional.scope;
+                data.breaking = conventional.breaking;
+            }
+        }
+
+        // notes
+        if !commit_result.notes.is_empty() {
+            if let Some(data) = graph.commit_data_mut(commit_id_str) {
+                data.notes = commit_result.notes.clone();
+            }
+        }
+
         // issues
         for issue in &commit_result.issues {
             graph.add_issue_node(issue);
@@ -115,32 +221,71 @@ fn walk_dfs(conf: Config, repo: &Repository) -> RelationGraph {
             graph.add_edge_commit2issue(commit_id_str, issue);
         }
 
+        // symbols
+        for (file, symbol) in &commit_result.symbols {
+            graph.add_symbol_node(symbol);
+            graph.add_edge_file2symbol(file, symbol);
+            graph.add_edge_symbol2commit(symbol, commit_id_str);
+            for issue in &commit_result.issues {
+                graph.add_edge_symbol2issue(symbol, issue);
+            }
+        }
+
+        // line ranges
+        for (file, ranges) in &commit_result.line_ranges {
+            for (start, end) in ranges {
+                graph.add_file_range(file, commit_id_str, *start, *end);
+            }
+        }
+
+        // churn
+        for (file, lines) in &commit_result.churn {
+            graph.add_file_churn(file, commit_id_str, *lines);
+        }
+
         // author
         graph.add_author_node(author_str);
         graph.add_edge_author2commit(author_str, commit_id_str);
 
-        counter += 1;
-        if conf.progress {
-            pb.inc(1);
+        // trailers
+        if !conf.trailers.is_empty() {
+            for (role, person) in
+                parse_trailers(commit.message().unwrap_or_default(), &conf.trailers)
+            {
+                graph.add_person_node(&person);
+                graph.add_edge_person2commit(&person, commit_id_str, &role);
+            }
         }
+
+        graph.last_commit = Some(commit_id_str.clone());
+        observer.on_commit(&CommitEvent {
+            commit_id: commit_id_str,
+            files: &commit_result.files,
+            issues: &commit_result.issues,
+            elapsed: start_time.elapsed(),
+        });
+
+        counter += 1;
         if counter > conf.depth {
             break;
         }
     }
 
-    return graph;
-}
+    if conf.blame && graph.complete {
+        graph.complete = blame_pass(repo, &head, &issue_regex, &conf, start_time, &mut graph);
+    }
+    observer.on_finish();
 
-fn create_progress(size: u64) -> ProgressBar {
-    let pb = ProgressBar::new(size);
-    pb.set_style(ProgressStyle::with_template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {items}/{total_items} ({eta})")
-        .unwrap()
-        .with_key("eta", |state: &ProgressState, w: &mut dyn Write| write!(w, "{:.1}s", state.eta().as_secs_f64()).unwrap())
-        .progress_chars("#>-"));
-    return pb;
+    return graph;
 }
 
-fn process_commit(repo: &Repository, commit: &Commit, re: &Regex, conf: &Config) -> CommitResult {
+fn process_commit(
+    repo: &Repository,
+    commit: &Commit,
+    re: &Regex,
+    conf: &Config,
+    tracker: &mut LineTracker,
+) -> CommitResult {
     if let Some(parent) = commit.parent(0).ok() {
         // TODO: seems that we should do a cache here
         // libgit2 also has a cache too:
@@ -158,6 +303,10 @@ fn process_commit(repo: &Repository, commit: &Commit, re: &Regex, conf: &Config)
         opts.include_ignored(false);
         opts.ignore_filemode(true);
         opts.force_text(true);
+        if conf.symbols || conf.line_ranges {
+            // exact hunk ranges
+            opts.context_lines(0);
+        }
 
         // fast but not very fast ...
         // when the trees are large
@@ -178,15 +327,59 @@ fn process_commit(repo: &Repository, commit: &Commit, re: &Regex, conf: &Config)
             })
             .collect();
 
+        let mut symbols = Vec::new();
+        let mut line_ranges = Vec::new();
+        let mut churn = Vec::new();
+        if conf.symbols || conf.line_ranges || conf.churn {
+            for (idx, delta) in changes.deltas().enumerate() {
+                let file = match delta.new_file().path() {
+                    Some(path) => path.to_string_lossy().into_owned(),
+                    None => continue,
+                };
+                let patch = match Patch::from_diff(&changes, idx) {
+                    Ok(Some(patch)) => patch,
+                    _ => continue,
+                };
+                if conf.churn {
+                    if let Ok((_, additions, deletions)) = patch.line_stats() {
+                        churn.push((file.clone(), additions + deletions));
+                    }
+                }
+                if conf.symbols {
+                    for symbol in extract_symbols(&patch, &file) {
+                        symbols.push((file.clone(), symbol));
+                    }
+                }
+                if conf.line_ranges {
+                    let hunks = (0..patch.num_hunks())
+                        .filter_map(|hunk_idx| patch.hunk(hunk_idx).ok())
+                        .map(|(hunk, _)| Hunk {
+                            old_start: hunk.old_start(),
+                            old_lines: hunk.old_lines(),
+                            new_start: hunk.new_start(),
+                            new_lines: hunk.new_lines(),
+                        })
+                        .collect();
+                    let ranges = tracker.track(&file, hunks);
+                    if !ranges.is_empty() {
+                        line_ranges.push((file, ranges));
+                    }
+                }
+            }
+        }
+
         // Issue extract
-        let issues = re
-            .find_iter(commit.message().unwrap_or_default())
-            .map(|mat| mat.as_str().to_string())
-            .collect();
+        // notes can link issues after the fact
+        let notes = read_notes(repo, commit.id(), &conf.notes_refs);
+        let issues = commit_issues(re, commit.message().unwrap_or_default(), &notes);
 
         return CommitResult {
             files: changed_files,
             issues,
+            symbols,
+            line_ranges,
+            notes,
+            churn,
         };
     }
     CommitResult::default()
@@ -0,0 +1,25 @@
+use git2::{Oid, Repository};
+use regex::Regex;
+
+/// note messages attached to a commit, from each notes ref (e.g. `refs/notes/commits`)
+pub(crate) fn read_notes(repo: &Repository, commit_id: Oid, notes_refs: &[String]) -> Vec<String> {
+    return notes_refs
+        .iter()
+        .filter_map(|notes_ref| repo.find_note(Some(notes_ref), commit_id).ok())
+        .filter_map(|note| note.message().map(|message| message.to_string()))
+        .collect();
+}
+
+/// issue references in a commit message and its notes, each once
+pub(crate) fn commit_issues(re: &Regex, message: &str, notes: &[String]) -> Vec<String> {
+    let mut issues: Vec<String> = Vec::new();
+    for text in std::iter::once(message).chain(notes.iter().map(|note| note.as_str())) {
+        for mat in re.find_iter(text) {
+            let issue = mat.as_str().to_string();
+            if !issues.contains(&issue) {
+                issues.push(issue);
+            }
+        }
+    }
+    return issues;
+}
@@ -0,0 +1,83 @@
+use indicatif::{ProgressBar, ProgressState, ProgressStyle};
+use std::fmt::Write;
+use std::time::Duration;
+
+/// what an observer receives for each commit that made it into the graph
+pub struct CommitEvent<'a> {
+    pub commit_id: &'a str,
+    pub files: &'a [String],
+    pub issues: &'a [String],
+    /// time since the walk started
+    pub elapsed: Duration,
+}
+
+/// hooks into the walking process, for custom UI or logs
+pub trait WalkObserver {
+    /// ask the collector to count commits before walking, which costs an extra revwalk
+    fn needs_total(&self) -> bool {
+        false
+    }
+
+    /// `total` is only available when it is knowable (see `needs_total`)
+    fn on_start(&mut self, _total: Option<u64>) {}
+
+    fn on_commit(&mut self, event: &CommitEvent);
+
+    /// commit visited but filtered out (excluded, or no file changed)
+    fn on_skip(&mut self, _commit_id: &str) {}
+
+    fn on_finish(&mut self) {}
+}
+
+pub struct NoopObserver {}
+
+impl WalkObserver for NoopObserver {
+    fn on_commit(&mut self, _event: &CommitEvent) {}
+}
+
+/// the default terminal progress bar used by the cli
+pub struct ProgressBarObserver {
+    pb: ProgressBar,
+    fallback_size: u64,
+}
+
+impl ProgressBarObserver {
+    /// `fallback_size` is used as length when the real total is unknown
+    pub fn new(fallback_size: u64) -> ProgressBarObserver {
+        return ProgressBarObserver {
+            pb: create_progress(fallback_size),
+            fallback_size,
+        };
+    }
+}
+
+impl WalkObserver for ProgressBarObserver {
+    fn needs_total(&self) -> bool {
+        true
+    }
+
+    fn on_start(&mut self, total: Option<u64>) {
+        self.pb.set_length(total.unwrap_or(self.fallback_size));
+    }
+
+    fn on_commit(&mut self, _event: &CommitEvent) {
+        self.pb.inc(1);
+    }
+
+    fn on_skip(&mut self, _commit_id: &str) {
+        self.pb.inc(1);
+    }
+
+    fn on_finish(&mut self) {
+        self.pb.finish();
+    }
+}
+
+fn create_progress(size: u64) -> ProgressBar {
+    let pb = ProgressBar::new(size);
+    pb.set_style(ProgressStyle::with_template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {items}/{total_items} ({eta})")
+        .unwrap()
+        .with_key("eta", |state: &ProgressState, w: &mut dyn Write| write!(w, "{:.1}s", state.eta().as_secs_f64()).unwrap())
+        .progress_chars("#>-"));
+    return pb;
+}
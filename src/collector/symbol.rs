@@ -0,0 +1,74 @@
+use git2::Patch;
+use regex::Regex;
+use std::collections::HashMap;
+
+lazy_static::lazy_static! {
+    // lightweight function definition patterns, keyed by file extension
+    static ref SYMBOL_REGEX: HashMap<&'static str, Regex> = {
+        let rust = Regex::new(r"\bfn\s+([A-Za-z_]\w*)").unwrap();
+        let python = Regex::new(r"\bdef\s+([A-Za-z_]\w*)").unwrap();
+        let ruby = Regex::new(r"\bdef\s+(?:self\.)?([A-Za-z_]\w*[?!]?)").unwrap();
+        let go = Regex::new(r"\bfunc\s+(?:\([^)]*\)\s*)?([A-Za-z_]\w*)").unwrap();
+        let js = Regex::new(r"\bfunction\s*\*?\s*([A-Za-z_$][\w$]*)").unwrap();
+        let kotlin = Regex::new(r"\bfun\s+(?:<[^>]*>\s*)?([A-Za-z_]\w*)").unwrap();
+
+        let mut m = HashMap::new();
+        m.insert("rs", rust);
+        m.insert("py", python);
+        m.insert("rb", ruby);
+        m.insert("go", go);
+        for ext in ["js", "jsx", "mjs", "cjs", "ts", "tsx"] {
+            m.insert(ext, js.clone());
+        }
+        m.insert("kt", kotlin);
+        m
+    };
+}
+
+/// symbol names look like `src/foo.rs::parse_config`
+pub(crate) fn symbol_name(file: &str, func: &str) -> String {
+    return format!("{}::{}", file, func);
+}
+
+/// functions touched by a patch: the enclosing function from each hunk header,
+/// and function definitions inside changed lines.
+pub(crate) fn extract_symbols(patch: &Patch, file: &str) -> Vec<String> {
+    let ext = file.rsplit('.').next().unwrap_or_default();
+    let re = match SYMBOL_REGEX.get(ext) {
+        Some(re) => re,
+        None => return Vec::new(),
+    };
+
+    let mut funcs: Vec<String> = Vec::new();
+    let mut collect = |text: &str| {
+        for cap in re.captures_iter(text) {
+            let func = cap[1].to_string();
+            if !funcs.contains(&func) {
+                funcs.push(func);
+            }
+        }
+    };
+
+    for hunk_idx in 0..patch.num_hunks() {
+        let (hunk, line_count) = match patch.hunk(hunk_idx) {
+            Ok(hunk) => hunk,
+            Err(_) => continue,
+        };
+
+        // "@@ -1,2 +1,3 @@ fn foo() {"
+        let header = String::from_utf8_lossy(hunk.header());
+        if let Some(context) = header.splitn(3, "@@").nth(2) {
+            collect(context);
+        }
+
+        for line_idx in 0..line_count {
+            if let Ok(line) = patch.line_in_hunk(hunk_idx, line_idx) {
+                if line.origin() == '+' || line.origin() == '-' {
+                    collect(&String::from_utf8_lossy(line.content()));
+                }
+            }
+        }
+    }
+
+    return funcs.iter().map(|func| symbol_name(file, func)).collect();
+}
@@ -0,0 +1,22 @@
+/// (role, person) pairs from trailers like `Reviewed-by: Name <mail>`,
+/// `keys` are matched case-insensitively and the configured spelling is used as role
+pub(crate) fn parse_trailers(message: &str, keys: &[String]) -> Vec<(String, String)> {
+    let mut ret = Vec::new();
+    for line in message.lines() {
+        let (key, value) = match line.split_once(':') {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        if let Some(role) = keys
+            .iter()
+            .find(|each| each.eq_ignore_ascii_case(key.trim()))
+        {
+            ret.push((role.clone(), value.to_string()));
+        }
+    }
+    return ret;
+}
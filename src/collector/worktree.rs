@@ -0,0 +1,24 @@
+use crate::collector::diff::diff_files;
+use git2::{DiffOptions, Repository};
+
+/// files changed in the working tree and index against HEAD, including untracked ones.
+/// nothing is written to the graph.
+pub fn worktree_changed_files(
+    repo_path: &str,
+    path_specs: &[String],
+) -> Result<Vec<String>, git2::Error> {
+    let repo = Repository::open(repo_path)?;
+    let head_tree = repo.head()?.peel_to_tree()?;
+
+    let mut opts = DiffOptions::default();
+    for each in path_specs {
+        opts.pathspec(each);
+    }
+    opts.include_untracked(true);
+    opts.recurse_untracked_dirs(true);
+    opts.include_ignored(false);
+    opts.ignore_filemode(true);
+
+    let changes = repo.diff_tree_to_workdir_with_index(Some(&head_tree), Some(&mut opts))?;
+    return Ok(diff_files(&changes));
+}
@@ -2,10 +2,17 @@ use clap::Parser;
 use cupido::collector::config::get_collector;
 use cupido::collector::config::Collect;
 use cupido::collector::config::Config;
+use cupido::collector::diff::{patch_changed_files, range_changed_files, rev_added_removed_files};
+use cupido::relation::graph::{
+    BusFactor, BusFactorOptions, ChangeImpact, CommunityOptions, GraphDiffOptions, NodeKind,
+    RelationGraph,
+};
+use cupido::relation::query::Query;
 use cupido::server::app::server_main;
 use cupido::server::config::ServerConfig;
-use std::time::Instant;
-use tracing::info;
+use std::io::Read;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
 
 #[derive(Parser, Debug)]
 #[clap(
@@ -27,8 +34,30 @@ enum SubCommand {
     /// Extract file based mapping
     #[clap(name = "map")]
     Map(MapCommand),
-    // Diff (subset of `Extract` commands
-    // TODO
+
+    /// Blast radius of a change: related issues, commits, authors and co-changed files
+    #[clap(name = "diff")]
+    Diff(DiffCommand),
+
+    /// Explain how two nodes are related, e.g. a file and an issue
+    #[clap(name = "path")]
+    Path(PathCommand),
+
+    /// Ad-hoc graph query, e.g. 'file[name~"src/"] -> commit[kind="fix"] -> issue | limit 10'
+    #[clap(name = "query")]
+    Query(QueryCommand),
+
+    /// Bus factor and knowledge concentration per file and directory
+    #[clap(name = "bus-factor")]
+    BusFactor(BusFactorCommand),
+
+    /// Clusters of co-changed files, to compare with the directory layout
+    #[clap(name = "communities")]
+    Communities(CommunitiesCommand),
+
+    /// Compare the graphs at two revs, e.g. last month's tag and HEAD
+    #[clap(name = "graph-diff")]
+    GraphDiff(GraphDiffCommand),
 }
 
 #[derive(Parser, Debug)]
@@ -56,6 +85,42 @@ struct CommonOptions {
     /// Depth Limit
     #[clap(long)]
     depth: Option<u32>,
+
+    /// Stop walking after N seconds and keep the partial graph
+    #[clap(long)]
+    time_budget: Option<u64>,
+
+    /// Create function level symbol nodes from diff hunks
+    #[clap(long)]
+    symbols: Option<bool>,
+
+    /// Store line ranges for range history queries, ignored with multi parents
+    #[clap(long)]
+    line_ranges: Option<bool>,
+
+    /// Blame files at HEAD for current ownership
+    #[clap(long)]
+    blame: Option<bool>,
+
+    /// Create scope nodes from conventional commits
+    #[clap(long)]
+    scope_nodes: Option<bool>,
+
+    /// Trailer keys creating person nodes, e.g. "Reviewed-by;Acked-by"
+    #[clap(long)]
+    trailers: Option<String>,
+
+    /// Notes refs read as commit annotations, e.g. "refs/notes/commits;refs/notes/ci"
+    #[clap(long)]
+    notes_refs: Option<String>,
+
+    /// Count changed lines per file and commit for hotness scoring
+    #[clap(long)]
+    churn: Option<bool>,
+
+    /// Walk from this rev (e.g. a tag) instead of HEAD
+    #[clap(long)]
+    rev: Option<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -73,35 +138,214 @@ struct MapCommand {
     common_options: CommonOptions,
 }
 
+#[derive(clap::ValueEnum, Clone, Debug)]
+enum OutputFormat {
+    Json,
+    Table,
+}
+
+#[derive(Parser, Debug)]
+struct DiffCommand {
+    #[clap(flatten)]
+    common_options: CommonOptions,
+
+    /// Revision range like `base..head`, reads a patch from stdin if missing
+    range: Option<String>,
+
+    /// Output format
+    #[clap(long, value_enum, default_value = "json")]
+    format: OutputFormat,
+
+    /// Max authors and co-changed files listed
+    #[clap(long, default_value_t = 10)]
+    top: usize,
+}
+
+#[derive(Parser, Debug)]
+struct PathCommand {
+    #[clap(flatten)]
+    common_options: CommonOptions,
+
+    /// Start node type: file, commit, issue, author ...
+    #[clap(long)]
+    from_type: NodeKind,
+
+    /// Start node name
+    #[clap(long)]
+    from: String,
+
+    /// End node type
+    #[clap(long)]
+    to_type: NodeKind,
+
+    /// End node name
+    #[clap(long)]
+    to: String,
+
+    /// Max edges between them
+    #[clap(long, default_value_t = 4)]
+    max_hops: usize,
+
+    /// Output format
+    #[clap(long, value_enum, default_value = "json")]
+    format: OutputFormat,
+}
+
+#[derive(Parser, Debug)]
+struct QueryCommand {
+    #[clap(flatten)]
+    common_options: CommonOptions,
+
+    /// Query expression
+    expr: String,
+
+    /// Output format
+    #[clap(long, value_enum, default_value = "json")]
+    format: OutputFormat,
+}
+
+#[derive(Parser, Debug)]
+struct BusFactorCommand {
+    #[clap(flatten)]
+    common_options: CommonOptions,
+
+    /// Share of commits the dominant authors must cover
+    #[clap(long, default_value_t = 0.5)]
+    coverage: f64,
+
+    /// Authors without commits for this many months count as gone
+    #[clap(long, default_value_t = 6)]
+    inactive_months: u32,
+
+    /// Directory depth of the report
+    #[clap(long, default_value_t = 1)]
+    dir_depth: usize,
+
+    /// Output format
+    #[clap(long, value_enum, default_value = "json")]
+    format: OutputFormat,
+
+    /// Max rows per section
+    #[clap(long, default_value_t = 10)]
+    top: usize,
+}
+
+#[derive(Parser, Debug)]
+struct CommunitiesCommand {
+    #[clap(flatten)]
+    common_options: CommonOptions,
+
+    /// Link files sharing nodes of this type: commit or issue
+    #[clap(long, default_value = "commit")]
+    via: NodeKind,
+
+    /// Shared nodes needed for a link
+    #[clap(long, default_value_t = 1)]
+    min_weight: usize,
+
+    /// Smallest cluster reported
+    #[clap(long, default_value_t = 2)]
+    min_size: usize,
+
+    /// Output format
+    #[clap(long, value_enum, default_value = "json")]
+    format: OutputFormat,
+}
+
+#[derive(Parser, Debug)]
+struct GraphDiffCommand {
+    #[clap(flatten)]
+    common_options: CommonOptions,
+
+    /// Older rev, e.g. a tag
+    from: String,
+
+    /// Newer rev, `--rev` or HEAD by default
+    to: Option<String>,
+
+    /// Smaller hotness changes are left out
+    #[clap(long, default_value_t = 1.0)]
+    min_hotness_delta: f64,
+
+    /// Output format
+    #[clap(long, value_enum, default_value = "json")]
+    format: OutputFormat,
+}
+
 fn main() {
     let cli: Cli = Cli::parse();
 
     match cli.cmd {
         SubCommand::Up(up_cmd) => handle_up(up_cmd),
         SubCommand::Map(map_cmd) => handle_map(map_cmd),
+        SubCommand::Diff(diff_cmd) => handle_diff(diff_cmd),
+        SubCommand::Path(path_cmd) => handle_path(path_cmd),
+        SubCommand::Query(query_cmd) => handle_query(query_cmd),
+        SubCommand::BusFactor(bus_cmd) => handle_bus_factor(bus_cmd),
+        SubCommand::Communities(communities_cmd) => handle_communities(communities_cmd),
+        SubCommand::GraphDiff(graph_diff_cmd) => handle_graph_diff(graph_diff_cmd),
     }
 }
 
-fn handle_map(map_command: MapCommand) {
-    info!("relation creating ...");
-    let mut conf = Config::default();
-    if let Some(ref user_issue_regex) = map_command.common_options.issue_regex {
-        conf.issue_regex = user_issue_regex.clone()
-    }
-    if let Some(ref repo_path) = map_command.common_options.repo_path {
-        conf.repo_path = repo_path.clone()
-    }
-    if let Some(ref path_specs) = map_command.common_options.path_specs {
-        conf.path_specs = path_specs.split(";").map(|a| a.into()).collect();
-    }
-    if let Some(ref multi_parents) = map_command.common_options.multi_parents {
-        conf.multi_parents = multi_parents.clone()
-    }
-    if let Some(ref depth) = map_command.common_options.depth {
-        conf.depth = depth.clone()
+impl CommonOptions {
+    fn to_config(&self) -> Config {
+        let mut conf = Config::default();
+        if let Some(ref user_issue_regex) = self.issue_regex {
+            conf.issue_regex = user_issue_regex.clone()
+        }
+        if let Some(ref repo_path) = self.repo_path {
+            conf.repo_path = repo_path.clone()
+        }
+        if let Some(ref path_specs) = self.path_specs {
+            conf.path_specs = path_specs.split(";").map(|a| a.into()).collect();
+        }
+        if let Some(ref multi_parents) = self.multi_parents {
+            conf.multi_parents = multi_parents.clone()
+        }
+        if let Some(ref progress) = self.progress {
+            conf.progress = progress.clone()
+        }
+        if let Some(ref depth) = self.depth {
+            conf.depth = depth.clone()
+        }
+        if let Some(ref time_budget) = self.time_budget {
+            conf.time_budget = Some(Duration::from_secs(*time_budget))
+        }
+        if let Some(ref symbols) = self.symbols {
+            conf.symbols = *symbols
+        }
+        if let Some(ref line_ranges) = self.line_ranges {
+            conf.line_ranges = *line_ranges
+        }
+        if let Some(ref blame) = self.blame {
+            conf.blame = *blame
+        }
+        if let Some(ref scope_nodes) = self.scope_nodes {
+            conf.scope_nodes = *scope_nodes
+        }
+        if let Some(ref trailers) = self.trailers {
+            conf.trailers = trailers.split(";").map(|a| a.into()).collect();
+        }
+        if let Some(ref notes_refs) = self.notes_refs {
+            conf.notes_refs = notes_refs.split(";").map(|a| a.into()).collect();
+        }
+        if let Some(ref churn) = self.churn {
+            conf.churn = *churn
+        }
+        if let Some(ref rev) = self.rev {
+            conf.rev = Some(rev.clone())
+        }
+        return conf;
     }
+}
 
-    info!("config: {:?}", map_command);
+fn create_graph(common_options: &CommonOptions) -> RelationGraph {
+    info!("config: {:?}", common_options);
+    return create_graph_with(common_options.to_config());
+}
+
+fn create_graph_with(conf: Config) -> RelationGraph {
+    info!("relation creating at {:?} ...", conf.rev);
     let start_time = Instant::now();
 
     let collector = get_collector();
@@ -111,7 +355,17 @@ fn handle_map(map_command: MapCommand) {
         start_time.elapsed(),
         graph.size()
     );
+    if !graph.is_complete() {
+        warn!(
+            "relation is partial, stopped at commit {:?}",
+            graph.last_commit()
+        );
+    }
+    return graph;
+}
 
+fn handle_map(map_command: MapCommand) {
+    let graph = create_graph(&map_command.common_options);
     let mapping = graph.export_file_issue_mapping();
 
     // to stdout
@@ -122,42 +376,272 @@ fn handle_map(map_command: MapCommand) {
 fn handle_up(up_cmd: UpCommand) {
     tracing_subscriber::fmt::init();
 
-    info!("relation creating ...");
-    let mut conf = Config::default();
-    if let Some(ref user_issue_regex) = up_cmd.common_options.issue_regex {
-        conf.issue_regex = user_issue_regex.clone()
+    let graph = create_graph(&up_cmd.common_options);
+    let mut server_conf = ServerConfig::new(graph);
+    if let Some(ref port) = up_cmd.port {
+        server_conf.port = *port
     }
-    if let Some(ref repo_path) = up_cmd.common_options.repo_path {
-        conf.repo_path = repo_path.clone()
+    info!("server up: http://127.0.0.1:{}", server_conf.port);
+    server_main(server_conf);
+}
+
+fn handle_diff(diff_cmd: DiffCommand) {
+    // changes first, no need to walk if it fails
+    let conf = diff_cmd.common_options.to_config();
+    let files = match &diff_cmd.range {
+        Some(range) => range_changed_files(&conf.repo_path, range, &conf.path_specs),
+        None => {
+            let mut patch = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut patch)
+                .expect("Failed to read patch from stdin");
+            patch_changed_files(&patch)
+        }
     }
-    if let Some(ref path_specs) = up_cmd.common_options.path_specs {
-        conf.path_specs = path_specs.split(";").map(|a| a.into()).collect();
+    .expect("Failed to get changed files");
+
+    let graph = create_graph(&diff_cmd.common_options);
+    let mut impact = graph.change_impact(&files);
+    impact.authors.truncate(diff_cmd.top);
+    impact.cochanged_files.truncate(diff_cmd.top);
+
+    match diff_cmd.format {
+        OutputFormat::Json => {
+            let json_string = serde_json::to_string(&impact).expect("Failed to serialize to JSON");
+            print!("{}", json_string);
+        }
+        OutputFormat::Table => print_impact_table(&impact),
     }
-    if let Some(ref multi_parents) = up_cmd.common_options.multi_parents {
-        conf.multi_parents = multi_parents.clone()
+}
+
+fn print_impact_table(impact: &ChangeImpact) {
+    println!("FILES ({})", impact.files.len());
+    for file in &impact.files {
+        if impact.unknown_files.contains(file) {
+            println!("  {} (new)", file);
+        } else {
+            println!("  {}", file);
+        }
     }
-    if let Some(ref progress) = up_cmd.common_options.progress {
-        conf.progress = progress.clone()
+    let sections = [
+        ("RELATED ISSUES", &impact.issues),
+        ("TOP AUTHORS", &impact.authors),
+        ("CO-CHANGED FILES NOT TOUCHED", &impact.cochanged_files),
+    ];
+    for (title, counts) in sections {
+        println!();
+        println!("{} ({})", title, counts.len());
+        for each in counts.iter() {
+            println!("  {:>5}  {}", each.count, each.name);
+        }
     }
-    if let Some(ref depth) = up_cmd.common_options.depth {
-        conf.depth = depth.clone()
+    println!();
+    println!("HISTORICAL COMMITS ({})", impact.commits.len());
+    for commit in &impact.commits {
+        println!("  {}", commit);
     }
+}
 
-    info!("config: {:?}", up_cmd);
-    let start_time = Instant::now();
+fn handle_path(path_cmd: PathCommand) {
+    let graph = create_graph(&path_cmd.common_options);
+    let path = graph
+        .shortest_path(
+            path_cmd.from_type,
+            &path_cmd.from,
+            path_cmd.to_type,
+            &path_cmd.to,
+            path_cmd.max_hops,
+        )
+        .expect("Node not found");
 
-    let collector = get_collector();
-    let graph = collector.walk(conf);
-    info!(
-        "relation ready in {:?}: {:?}",
-        start_time.elapsed(),
-        graph.size()
+    match path_cmd.format {
+        OutputFormat::Json => {
+            let json_string = serde_json::to_string(&path).expect("Failed to serialize to JSON");
+            print!("{}", json_string);
+        }
+        OutputFormat::Table => match path {
+            Some(path) => {
+                for (idx, node) in path.nodes.iter().enumerate() {
+                    if idx > 0 {
+                        println!("  | {}", path.edges[idx - 1]);
+                    }
+                    println!("{} ({})", node.name, node.kind);
+                }
+            }
+            None => println!("not related within {} hops", path_cmd.max_hops),
+        },
+    }
+}
+
+fn handle_query(query_cmd: QueryCommand) {
+    // fail fast on typos before the walk
+    let query: Query = match query_cmd.expr.parse() {
+        Ok(query) => query,
+        Err(error) => {
+            eprintln!("{}", error);
+            eprintln!("{}", query_cmd.expr);
+            eprintln!("{}^", " ".repeat(error.position));
+            std::process::exit(1);
+        }
+    };
+    let graph = create_graph(&query_cmd.common_options);
+    let result = graph.run_query(&query);
+
+    match query_cmd.format {
+        OutputFormat::Json => {
+            let json_string = serde_json::to_string(&result).expect("Failed to serialize to JSON");
+            print!("{}", json_string);
+        }
+        OutputFormat::Table => {
+            for each in &result {
+                println!("{:>6}  {}", each.count, each.name);
+            }
+        }
+    }
+}
+
+fn handle_bus_factor(bus_cmd: BusFactorCommand) {
+    let graph = create_graph(&bus_cmd.common_options);
+    let options = BusFactorOptions {
+        coverage: bus_cmd.coverage,
+        inactive_months: bus_cmd.inactive_months,
+        dir_depth: bus_cmd.dir_depth,
+    };
+    let mut report = graph.bus_factor_report(&options);
+    report.files.truncate(bus_cmd.top);
+    report.dirs.truncate(bus_cmd.top);
+    report.orphaned_files.truncate(bus_cmd.top);
+
+    match bus_cmd.format {
+        OutputFormat::Json => {
+            let json_string = serde_json::to_string(&report).expect("Failed to serialize to JSON");
+            print!("{}", json_string);
+        }
+        OutputFormat::Table => {
+            let sections = [
+                ("DIRECTORIES", &report.dirs),
+                ("FILES", &report.files),
+                ("ORPHANED FILES", &report.orphaned_files),
+            ];
+            for (title, rows) in sections {
+                println!("{} ({})", title, rows.len());
+                for each in rows {
+                    print_bus_factor_row(each);
+                }
+            }
+        }
+    }
+}
+
+fn print_bus_factor_row(bus_factor: &BusFactor) {
+    println!(
+        "  {:>3}  {:>5.0}%  {:>6}  {}  ({})",
+        bus_factor.bus_factor,
+        bus_factor.main_share * 100.0,
+        bus_factor.commits,
+        bus_factor.name,
+        bus_factor.main_author.as_deref().unwrap_or("-"),
     );
+}
 
-    let mut server_conf = ServerConfig::new(graph);
-    if let Some(ref port) = up_cmd.port {
-        server_conf.port = *port
+fn handle_communities(communities_cmd: CommunitiesCommand) {
+    let graph = create_graph(&communities_cmd.common_options);
+    let options = CommunityOptions {
+        via: communities_cmd.via,
+        min_weight: communities_cmd.min_weight,
+        min_size: communities_cmd.min_size,
+        ..CommunityOptions::default()
+    };
+    let communities = graph.communities(&options);
+
+    match communities_cmd.format {
+        OutputFormat::Json => {
+            let json_string =
+                serde_json::to_string(&communities).expect("Failed to serialize to JSON");
+            print!("{}", json_string);
+        }
+        OutputFormat::Table => {
+            for community in &communities {
+                println!(
+                    "#{} {} files, {:.0}% in {}",
+                    community.id,
+                    community.files.len(),
+                    community.dir_purity * 100.0,
+                    community
+                        .dirs
+                        .first()
+                        .map(|dir| dir.name.as_str())
+                        .unwrap_or("-"),
+                );
+                for file in &community.files {
+                    println!("  {}", file);
+                }
+                let issues: Vec<&str> = community.issues.iter().map(|i| i.name.as_str()).collect();
+                let authors: Vec<&str> =
+                    community.authors.iter().map(|a| a.name.as_str()).collect();
+                println!("  issues: {}", issues.join(", "));
+                println!("  authors: {}", authors.join(", "));
+            }
+        }
+    }
+}
+
+fn handle_graph_diff(graph_diff_cmd: GraphDiffCommand) {
+    let conf = graph_diff_cmd.common_options.to_config();
+    let (repo_path, path_specs) = (conf.repo_path.clone(), conf.path_specs.clone());
+    let mut older_conf = conf.clone();
+    older_conf.rev = Some(graph_diff_cmd.from.clone());
+    let mut newer_conf = conf;
+    if let Some(to) = &graph_diff_cmd.to {
+        newer_conf.rev = Some(to.clone());
+    }
+    let older = create_graph_with(older_conf);
+    let newer = create_graph_with(newer_conf);
+
+    let options = GraphDiffOptions {
+        min_hotness_delta: graph_diff_cmd.min_hotness_delta,
+        ..GraphDiffOptions::default()
+    };
+    let mut diff = older.graph_diff(&newer, &options);
+    // both graphs keep deleted files, the trees tell what exists at each revision
+    let to = graph_diff_cmd.to.as_deref().unwrap_or("HEAD");
+    match rev_added_removed_files(&repo_path, &graph_diff_cmd.from, to, &path_specs) {
+        Ok((added_files, removed_files)) => {
+            diff.added_files = added_files;
+            diff.removed_files = removed_files;
+        }
+        Err(err) => warn!("failed to diff trees: {}", err),
+    }
+
+    match graph_diff_cmd.format {
+        OutputFormat::Json => {
+            let json_string = serde_json::to_string(&diff).expect("Failed to serialize to JSON");
+            print!("{}", json_string);
+        }
+        OutputFormat::Table => {
+            let sections = [
+                ("ADDED FILES", &diff.added_files),
+                ("REMOVED FILES", &diff.removed_files),
+                ("NEW ISSUES", &diff.new_issues),
+                ("NEW AUTHORS", &diff.new_authors),
+            ];
+            for (title, rows) in sections {
+                println!("{} ({})", title, rows.len());
+                for each in rows {
+                    println!("  {}", each);
+                }
+            }
+            println!("NEW AUTHOR FILES ({})", diff.new_author_files.len());
+            for each in &diff.new_author_files {
+                println!("  {} -> {} ({})", each.author, each.file, each.commits);
+            }
+            println!("HOTNESS CHANGES ({})", diff.hotness_changes.len());
+            for each in &diff.hotness_changes {
+                println!(
+                    "  {:>+8.2}  {:>8.2} -> {:<8.2}  {}",
+                    each.delta, each.before, each.after, each.file
+                );
+            }
+        }
     }
-    info!("server up: http://127.0.0.1:{}", server_conf.port);
-    server_main(server_conf);
 }
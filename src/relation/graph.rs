@@ -3,29 +3,117 @@ use petgraph::graph::{NodeIndex, UnGraph};
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
-use std::sync::Arc;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Clone)]
 pub(crate) enum NodeType {
     File(Option<FileData>),
     Commit(Option<CommitData>),
     Issue(Option<IssueData>),
     Author(Option<AuthorData>),
+    Symbol(Option<SymbolData>),
+    Scope(Option<ScopeData>),
+    Person(Option<PersonData>),
+}
+
+/// public, data-less version of `NodeType`
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeKind {
+    File,
+    Commit,
+    Issue,
+    Author,
+    Symbol,
+    Scope,
+    Person,
+}
+
+impl NodeType {
+    pub(crate) fn kind(&self) -> NodeKind {
+        return match self {
+            NodeType::File(_) => NodeKind::File,
+            NodeType::Commit(_) => NodeKind::Commit,
+            NodeType::Issue(_) => NodeKind::Issue,
+            NodeType::Author(_) => NodeKind::Author,
+            NodeType::Symbol(_) => NodeKind::Symbol,
+            NodeType::Scope(_) => NodeKind::Scope,
+            NodeType::Person(_) => NodeKind::Person,
+        };
+    }
+}
+
+impl FromStr for NodeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return match s.to_lowercase().as_str() {
+            "file" => Ok(NodeKind::File),
+            "commit" => Ok(NodeKind::Commit),
+            "issue" => Ok(NodeKind::Issue),
+            "author" => Ok(NodeKind::Author),
+            "symbol" => Ok(NodeKind::Symbol),
+            "scope" => Ok(NodeKind::Scope),
+            "person" => Ok(NodeKind::Person),
+            _ => Err(format!("unknown node type: {}", s)),
+        };
+    }
+}
+
+impl Display for NodeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format!("{:?}", self).to_lowercase())
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub(crate) struct FileData {
+    pub(crate) line_ranges: Vec<LineRange>,
+    // from blame at HEAD
+    pub(crate) total_lines: usize,
+    pub(crate) author_lines: Vec<(String, usize)>,
+    pub(crate) issue_lines: Vec<(String, usize)>,
+    // lines added + deleted, per commit
+    pub(crate) churn: Vec<(Arc<String>, usize)>,
 }
 
-#[derive(PartialEq, Eq, Debug)]
-pub(crate) struct FileData {}
+/// lines (inclusive, HEAD numbering) last touched by a commit
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub(crate) struct LineRange {
+    pub(crate) commit: Arc<String>,
+    pub(crate) start: u32,
+    pub(crate) end: u32,
+}
 
-#[derive(PartialEq, Eq, Debug)]
-pub(crate) struct CommitData {}
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub(crate) struct CommitData {
+    // conventional commit
+    pub(crate) kind: Option<String>,
+    pub(crate) scope: Option<String>,
+    pub(crate) breaking: bool,
+    // from git notes
+    pub(crate) notes: Vec<String>,
+    // commit time, seconds since epoch
+    pub(crate) time: Option<i64>,
+}
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub(crate) struct IssueData {}
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub(crate) struct AuthorData {}
 
-#[derive(Debug)]
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub(crate) struct SymbolData {}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub(crate) struct ScopeData {}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub(crate) struct PersonData {}
+
+#[derive(Debug, Clone)]
 pub(crate) enum EdgeType {
     // core
     File2Commit,
@@ -34,11 +122,32 @@ pub(crate) enum EdgeType {
 
     // options
     Author2Commit,
+    File2Symbol,
+    Symbol2Commit,
+    Symbol2Issue,
+    Scope2Commit,
+    File2Scope,
+    // with trailer roles, e.g. Reviewed-by, one person can hold several
+    Person2Commit(Vec<String>),
+}
+
+impl EdgeType {
+    /// trailer roles of a person edge, empty for every other edge
+    pub(crate) fn roles(&self) -> &[String] {
+        return match self {
+            EdgeType::Person2Commit(roles) => roles,
+            _ => &[],
+        };
+    }
 }
 
 impl Display for EdgeType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            // the variant name only, roles are a field of their own
+            EdgeType::Person2Commit(_) => write!(f, "Person2Commit"),
+            _ => write!(f, "{:?}", self),
+        }
     }
 }
 
@@ -61,8 +170,28 @@ pub struct RelationGraph {
     pub(crate) commit_mapping: NodeMapping,
     pub(crate) issue_mapping: NodeMapping,
     pub(crate) author_mapping: NodeMapping,
+    pub(crate) symbol_mapping: NodeMapping,
+    pub(crate) scope_mapping: NodeMapping,
+    pub(crate) person_mapping: NodeMapping,
     pub(crate) g: UnGraph<NodeData, EdgeType>,
     pub(crate) conf: CollectorConfig,
+    // false if the walk was cancelled or ran out of time
+    pub(crate) complete: bool,
+    pub(crate) last_commit: Option<String>,
+    pub(crate) index: DerivedIndex,
+}
+
+/// derived metrics, computed on first use and dropped on any mutation
+#[derive(Default)]
+pub(crate) struct DerivedIndex {
+    pub(crate) hot_ranks: OnceLock<HashMap<String, usize>>,
+    pub(crate) hotness: OnceLock<HotIndex>,
+}
+
+/// `file_hotness` with the default model, hottest first
+pub(crate) struct HotIndex {
+    pub(crate) files: Vec<Hotness>,
+    pub(crate) positions: HashMap<String, usize>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -71,3 +200,434 @@ pub struct GraphSize {
     pub(crate) commit_size: usize,
     pub(crate) issue_size: usize,
 }
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LineShare {
+    pub name: String,
+    pub lines: usize,
+    pub share: f64,
+}
+
+/// current ownership of a file, by surviving lines at HEAD
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct FileOwners {
+    pub total_lines: usize,
+    pub authors: Vec<LineShare>,
+    pub issues: Vec<LineShare>,
+}
+
+/// what we know about a commit besides its relations
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct CommitInfo {
+    pub kind: Option<String>,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub notes: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct PersonRole {
+    pub person: String,
+    pub role: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RelatedCount {
+    pub name: String,
+    pub count: usize,
+}
+
+/// what a change set (e.g. uncommitted changes) relates to in history
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct ChangeImpact {
+    pub files: Vec<String>,
+    /// changed files never seen in history, e.g. new files
+    pub unknown_files: Vec<String>,
+    pub issues: Vec<RelatedCount>,
+    pub commits: Vec<String>,
+    pub authors: Vec<RelatedCount>,
+    /// files changed together with the change set in history, but not in it
+    pub cochanged_files: Vec<RelatedCount>,
+}
+
+/// logical coupling of two files, from commits changing both
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Coupling {
+    pub file: String,
+    pub coupled: String,
+    /// commits changing both files
+    pub support: usize,
+    /// support / commits of `file`
+    pub confidence: f64,
+    /// support / commits of `coupled`
+    pub reverse_confidence: f64,
+    /// how much more often they change together than by chance
+    pub lift: f64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PathNode {
+    #[serde(rename = "type")]
+    pub kind: NodeKind,
+    pub name: String,
+}
+
+/// a chain explaining why two nodes are related, `edges[i]` links `nodes[i]` and `nodes[i + 1]`
+#[derive(Deserialize, Serialize, Debug)]
+pub struct NodePath {
+    pub nodes: Vec<PathNode>,
+    pub edges: Vec<String>,
+}
+
+/// weights for `file_hotness`, every signal decays with the age of its newest commit
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct HotnessModel {
+    pub commit_weight: f64,
+    pub issue_weight: f64,
+    /// per commit, applied to ln(1 + changed lines), needs churn collected
+    pub churn_weight: f64,
+    /// per distinct author
+    pub author_weight: f64,
+    /// None disables decay
+    pub half_life_days: Option<f64>,
+    /// reference time in seconds since epoch, defaults to the newest commit
+    pub now: Option<i64>,
+}
+
+impl Default for HotnessModel {
+    fn default() -> Self {
+        return HotnessModel {
+            commit_weight: 1.0,
+            issue_weight: 1.0,
+            churn_weight: 0.0,
+            author_weight: 0.0,
+            half_life_days: Some(180.0),
+            now: None,
+        };
+    }
+}
+
+/// rank 1 is the hottest, equal scores share a rank
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Hotness {
+    pub name: String,
+    pub score: f64,
+    pub rank: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct BusFactorOptions {
+    /// share of commits the dominant authors must cover
+    pub coverage: f64,
+    /// authors without commits for this long count as gone
+    pub inactive_months: u32,
+    /// directory depth of the report
+    pub dir_depth: usize,
+}
+
+impl Default for BusFactorOptions {
+    fn default() -> Self {
+        return BusFactorOptions {
+            coverage: 0.5,
+            inactive_months: 6,
+            dir_depth: 1,
+        };
+    }
+}
+
+/// knowledge concentration of a file or directory
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BusFactor {
+    pub name: String,
+    pub commits: usize,
+    /// fewest authors covering the wanted share of commits
+    pub bus_factor: usize,
+    pub main_author: Option<String>,
+    pub main_share: f64,
+    pub dominant_authors: Vec<String>,
+    /// dominant authors without recent commits
+    pub inactive_authors: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct BusFactorReport {
+    /// lowest bus factor first
+    pub files: Vec<BusFactor>,
+    pub dirs: Vec<BusFactor>,
+    /// files whose dominant authors are all inactive
+    pub orphaned_files: Vec<BusFactor>,
+}
+
+/// weights for `experts_for`, unlike hotness churn counts by default.
+/// Without churn collected (`--churn`) only commits and recency score.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct ExpertModel {
+    /// per commit
+    pub commit_weight: f64,
+    /// per commit, applied to ln(1 + changed lines)
+    pub churn_weight: f64,
+    /// None disables decay
+    pub half_life_days: Option<f64>,
+    /// reference time in seconds since epoch, defaults to the newest commit
+    pub now: Option<i64>,
+}
+
+impl Default for ExpertModel {
+    fn default() -> Self {
+        return ExpertModel {
+            commit_weight: 1.0,
+            churn_weight: 0.5,
+            half_life_days: Some(180.0),
+            now: None,
+        };
+    }
+}
+
+/// what to find experts for
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "lowercase", tag = "type", content = "name")]
+pub enum ExpertTarget {
+    File(String),
+    /// every file under a directory
+    Dir(String),
+    Issue(String),
+}
+
+/// an author who knows a target, with the commits backing it up
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Expert {
+    pub author: String,
+    pub score: f64,
+    pub commits: usize,
+    /// changed lines in the target, 0 without churn collected
+    pub churn: usize,
+    pub last_time: Option<i64>,
+    /// newest first
+    pub evidence: Vec<String>,
+    pub explanation: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct CommunityOptions {
+    /// files are linked when they share a node of this type, commits or issues
+    pub via: NodeKind,
+    /// shared nodes needed for a link
+    pub min_weight: usize,
+    /// smaller clusters are left out of the result
+    pub min_size: usize,
+    pub max_iterations: usize,
+    /// characteristic issues and authors per cluster
+    pub top: usize,
+}
+
+impl Default for CommunityOptions {
+    fn default() -> Self {
+        return CommunityOptions {
+            via: NodeKind::Commit,
+            min_weight: 1,
+            min_size: 2,
+            max_iterations: 20,
+            top: 5,
+        };
+    }
+}
+
+/// files which change together, with what they have in common
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Community {
+    pub id: usize,
+    pub files: Vec<String>,
+    /// by member files linked
+    pub issues: Vec<RelatedCount>,
+    /// by commits on member files
+    pub authors: Vec<RelatedCount>,
+    /// parent directories of member files
+    pub dirs: Vec<RelatedCount>,
+    /// share of files in the most common directory, 1.0 matches the layout
+    pub dir_purity: f64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CentralityMetric {
+    PageRank,
+    /// exact Brandes, O(nodes * edges) on the `within` subgraph
+    Betweenness,
+    Degree,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct CentralityOptions {
+    pub metric: CentralityMetric,
+    /// type of the ranked nodes, always part of the subgraph
+    pub kind: NodeKind,
+    /// node types of the subgraph the metric is computed on,
+    /// file + issue finds files bridging otherwise unrelated issues
+    pub within: Vec<NodeKind>,
+    /// pagerank only
+    pub damping: f64,
+    pub max_iterations: usize,
+}
+
+impl Default for CentralityOptions {
+    fn default() -> Self {
+        return CentralityOptions {
+            metric: CentralityMetric::PageRank,
+            kind: NodeKind::File,
+            within: vec![NodeKind::File, NodeKind::Issue],
+            damping: 0.85,
+            max_iterations: 100,
+        };
+    }
+}
+
+/// rank 1 is the most central, equal scores share a rank
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Centrality {
+    pub name: String,
+    pub score: f64,
+    pub rank: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct GraphDiffOptions {
+    pub model: HotnessModel,
+    /// smaller hotness changes are left out
+    pub min_hotness_delta: f64,
+}
+
+impl Default for GraphDiffOptions {
+    fn default() -> Self {
+        return GraphDiffOptions {
+            model: HotnessModel::default(),
+            min_hotness_delta: 1.0,
+        };
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AuthorFile {
+    pub author: String,
+    pub file: String,
+    pub commits: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct HotnessChange {
+    pub file: String,
+    pub before: f64,
+    pub after: f64,
+    pub delta: f64,
+    pub before_rank: Option<usize>,
+    pub after_rank: Option<usize>,
+}
+
+/// what changed between an older and a newer graph
+#[derive(Deserialize, Serialize, Debug)]
+pub struct GraphDiff {
+    pub before: GraphSize,
+    pub after: GraphSize,
+    /// files only the newer graph has seen
+    pub added_files: Vec<String>,
+    /// files only the older graph has seen, e.g. out of a depth limited walk.
+    /// Both graphs keep deleted files, see `rev_added_removed_files` for the trees.
+    pub removed_files: Vec<String>,
+    pub new_issues: Vec<String>,
+    pub new_authors: Vec<String>,
+    /// authors touching a file for the first time
+    pub new_author_files: Vec<AuthorFile>,
+    /// largest change first
+    pub hotness_changes: Vec<HotnessChange>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct SubgraphOptions {
+    /// max edges away from the center
+    pub hops: usize,
+    /// node types to keep, empty for all, the center is always kept
+    pub kinds: Vec<NodeKind>,
+    /// nearest nodes are kept first
+    pub max_nodes: usize,
+}
+
+impl Default for SubgraphOptions {
+    fn default() -> Self {
+        return SubgraphOptions {
+            hops: 2,
+            kinds: Vec::new(),
+            max_nodes: 200,
+        };
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct GraphNode {
+    pub id: usize,
+    #[serde(rename = "type")]
+    pub kind: NodeKind,
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct GraphEdge {
+    pub source: usize,
+    pub target: usize,
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// trailer roles, e.g. `Reviewed-by`, person edges only
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
+}
+
+/// plain nodes + edges, e.g. for visualizations
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct GraphExport {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PrunePolicy {
+    /// commits older than this, in seconds since epoch
+    pub before: Option<i64>,
+    /// commits touching more files, e.g. formatting or vendoring
+    pub max_commit_files: Option<usize>,
+    /// matching authors (e.g. bots) are removed with their commits
+    pub author_regex: Option<String>,
+    /// finally remove nodes left without any edge
+    pub orphans: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct PruneReport {
+    pub commits: usize,
+    pub authors: usize,
+    pub orphans: usize,
+}
+
+/// something both graphs know differently, the merged graph keeps ours
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MergeConflict {
+    /// None for graph level values, e.g. the collector config
+    #[serde(rename = "type")]
+    pub kind: Option<NodeKind>,
+    pub name: String,
+    pub field: String,
+    pub ours: String,
+    pub theirs: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct MergeReport {
+    pub added_nodes: usize,
+    pub added_edges: usize,
+    pub conflicts: Vec<MergeConflict>,
+}
@@ -0,0 +1,192 @@
+use crate::relation::graph::{
+    BusFactor, BusFactorOptions, BusFactorReport, NodeType, RelationGraph,
+};
+use crate::relation::graph_hotness::dir_at_depth;
+use petgraph::graph::NodeIndex;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Error;
+
+const SECONDS_PER_MONTH: i64 = 30 * 86400;
+
+/// bus factor API
+impl RelationGraph {
+    pub(crate) fn file_commit_indexes(&self, file_index: NodeIndex) -> Vec<NodeIndex> {
+        return self
+            .g
+            .neighbors(file_index)
+            .filter(|node_index| matches!(self.g[*node_index]._node_type, NodeType::Commit(_)))
+            .collect();
+    }
+
+    /// files under `dir`, "." for all
+    pub(crate) fn dir_file_indexes(&self, dir: &str) -> Vec<NodeIndex> {
+        let prefix = format!("{}/", dir.trim_end_matches('/'));
+        return self
+            .file_mapping
+            .iter()
+            .filter(|(name, _)| dir == "." || name.starts_with(&prefix))
+            .map(|(_, node_index)| *node_index)
+            .collect();
+    }
+
+    /// latest commit time per author
+    fn author_last_active(&self) -> HashMap<NodeIndex, i64> {
+        let mut last_active = HashMap::new();
+        for author_index in self.author_mapping.values() {
+            let newest = self
+                .g
+                .neighbors(*author_index)
+                .filter_map(|commit| match &self.g[commit]._node_type {
+                    NodeType::Commit(Some(data)) => data.time,
+                    _ => None,
+                })
+                .max();
+            if let Some(newest) = newest {
+                last_active.insert(*author_index, newest);
+            }
+        }
+        return last_active;
+    }
+
+    fn bus_factor_of(
+        &self,
+        name: &str,
+        commits: &HashSet<NodeIndex>,
+        options: &BusFactorOptions,
+        last_active: &HashMap<NodeIndex, i64>,
+    ) -> BusFactor {
+        let mut counter: HashMap<NodeIndex, usize> = HashMap::new();
+        for commit in commits {
+            for neighbor in self.g.neighbors(*commit) {
+                if let NodeType::Author(_) = self.g[neighbor]._node_type {
+                    *counter.entry(neighbor).or_insert(0) += 1;
+                }
+            }
+        }
+        let mut sorted: Vec<(NodeIndex, usize)> = counter.into_iter().collect();
+        sorted.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| self.g[a.0].name.cmp(&self.g[b.0].name))
+        });
+
+        let total = commits.len();
+        let wanted = options.coverage * total as f64;
+        let mut covered = 0;
+        let mut dominant = Vec::new();
+        for (author, count) in &sorted {
+            if covered as f64 >= wanted && !dominant.is_empty() {
+                break;
+            }
+            covered += count;
+            dominant.push(*author);
+        }
+
+        // relative to the newest commit, so old snapshots still make sense
+        let now = last_active.values().max().copied().unwrap_or(0);
+        let threshold = now - options.inactive_months as i64 * SECONDS_PER_MONTH;
+        let inactive_authors = dominant
+            .iter()
+            .filter(|author| {
+                last_active
+                    .get(author)
+                    .is_some_and(|time| *time < threshold)
+            })
+            .map(|author| self.g[*author].name.to_string())
+            .collect();
+
+        return BusFactor {
+            name: name.to_string(),
+            commits: total,
+            bus_factor: dominant.len(),
+            main_author: sorted
+                .first()
+                .map(|(author, _)| self.g[*author].name.to_string()),
+            main_share: match sorted.first() {
+                Some((_, count)) if total > 0 => *count as f64 / total as f64,
+                _ => 0.0,
+            },
+            dominant_authors: dominant
+                .iter()
+                .map(|author| self.g[*author].name.to_string())
+                .collect(),
+            inactive_authors,
+        };
+    }
+
+    pub fn file_bus_factor(
+        &self,
+        file_name: &String,
+        options: &BusFactorOptions,
+    ) -> Result<BusFactor, Error> {
+        let file_index = match self.file_mapping.get(file_name) {
+            Some(file_index) => *file_index,
+            None => return Err(Error::default()),
+        };
+        let commits = self.file_commit_indexes(file_index).into_iter().collect();
+        return Ok(self.bus_factor_of(file_name, &commits, options, &self.author_last_active()));
+    }
+
+    /// all commits touching files under `dir`, "." for the whole repo
+    pub fn dir_bus_factor(
+        &self,
+        dir: &str,
+        options: &BusFactorOptions,
+    ) -> Result<BusFactor, Error> {
+        let files = self.dir_file_indexes(dir);
+        if files.is_empty() {
+            return Err(Error::default());
+        }
+        let commits = files
+            .into_iter()
+            .flat_map(|file_index| self.file_commit_indexes(file_index))
+            .collect();
+        return Ok(self.bus_factor_of(dir, &commits, options, &self.author_last_active()));
+    }
+
+    pub fn bus_factor_report(&self, options: &BusFactorOptions) -> BusFactorReport {
+        let last_active = self.author_last_active();
+        let by_risk = |a: &BusFactor, b: &BusFactor| {
+            a.bus_factor
+                .cmp(&b.bus_factor)
+                .then_with(|| b.commits.cmp(&a.commits))
+                .then_with(|| a.name.cmp(&b.name))
+        };
+
+        let mut files: Vec<BusFactor> = self
+            .file_mapping
+            .iter()
+            .map(|(name, file_index)| {
+                let commits = self.file_commit_indexes(*file_index).into_iter().collect();
+                return self.bus_factor_of(name, &commits, options, &last_active);
+            })
+            .collect();
+        files.sort_by(by_risk);
+
+        let mut dir_commits: HashMap<String, HashSet<NodeIndex>> = HashMap::new();
+        for (name, file_index) in &self.file_mapping {
+            dir_commits
+                .entry(dir_at_depth(name, options.dir_depth))
+                .or_default()
+                .extend(self.file_commit_indexes(*file_index));
+        }
+        let mut dirs: Vec<BusFactor> = dir_commits
+            .iter()
+            .map(|(dir, commits)| self.bus_factor_of(dir, commits, options, &last_active))
+            .collect();
+        dirs.sort_by(by_risk);
+
+        let orphaned_files = files
+            .iter()
+            .filter(|each| {
+                !each.dominant_authors.is_empty()
+                    && each.inactive_authors.len() == each.dominant_authors.len()
+            })
+            .cloned()
+            .collect();
+        return BusFactorReport {
+            files,
+            dirs,
+            orphaned_files,
+        };
+    }
+}
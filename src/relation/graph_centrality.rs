@@ -0,0 +1,151 @@
+use crate::relation::graph::{
+    Centrality, CentralityMetric, CentralityOptions, NodeKind, RelationGraph,
+};
+use petgraph::graph::NodeIndex;
+use std::collections::{HashMap, VecDeque};
+
+/// centrality API
+impl RelationGraph {
+    /// nodes of the wanted types, and their adjacency by position
+    fn induced_subgraph(&self, within: &[NodeKind]) -> (Vec<NodeIndex>, Vec<Vec<usize>>) {
+        let nodes: Vec<NodeIndex> = self
+            .g
+            .node_indices()
+            .filter(|node_index| within.contains(&self.g[*node_index]._node_type.kind()))
+            .collect();
+        let positions: HashMap<NodeIndex, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(position, node_index)| (*node_index, position))
+            .collect();
+        let adjacency = nodes
+            .iter()
+            .map(|node_index| {
+                self.g
+                    .neighbors(*node_index)
+                    .filter_map(|neighbor| positions.get(&neighbor).copied())
+                    .collect()
+            })
+            .collect();
+        return (nodes, adjacency);
+    }
+
+    fn degree_scores(adjacency: &[Vec<usize>]) -> Vec<f64> {
+        let scale = (adjacency.len().max(2) - 1) as f64;
+        return adjacency
+            .iter()
+            .map(|neighbors| neighbors.len() as f64 / scale)
+            .collect();
+    }
+
+    fn pagerank_scores(adjacency: &[Vec<usize>], damping: f64, max_iterations: usize) -> Vec<f64> {
+        let n = adjacency.len();
+        if n == 0 {
+            return Vec::new();
+        }
+        let mut scores = vec![1.0 / n as f64; n];
+        for _ in 0..max_iterations {
+            // isolated nodes spread their score evenly
+            let dangling: f64 = adjacency
+                .iter()
+                .zip(&scores)
+                .filter(|(neighbors, _)| neighbors.is_empty())
+                .map(|(_, score)| score)
+                .sum();
+            let base = (1.0 - damping + damping * dangling) / n as f64;
+            let mut next = vec![base; n];
+            for (node, neighbors) in adjacency.iter().enumerate() {
+                let share = damping * scores[node] / neighbors.len().max(1) as f64;
+                for neighbor in neighbors {
+                    next[*neighbor] += share;
+                }
+            }
+            let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+            scores = next;
+            if change < 1e-9 {
+                break;
+            }
+        }
+        return scores;
+    }
+
+    /// Brandes, normalized to 0..1
+    fn betweenness_scores(adjacency: &[Vec<usize>]) -> Vec<f64> {
+        let n = adjacency.len();
+        let mut scores = vec![0.0; n];
+        for source in 0..n {
+            let mut stack: Vec<usize> = Vec::new();
+            let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+            let mut paths = vec![0.0; n];
+            let mut distance: Vec<Option<usize>> = vec![None; n];
+            paths[source] = 1.0;
+            distance[source] = Some(0);
+
+            let mut queue = VecDeque::from([source]);
+            while let Some(node) = queue.pop_front() {
+                stack.push(node);
+                let next_distance = distance[node].unwrap() + 1;
+                for neighbor in &adjacency[node] {
+                    if distance[*neighbor].is_none() {
+                        distance[*neighbor] = Some(next_distance);
+                        queue.push_back(*neighbor);
+                    }
+                    if distance[*neighbor] == Some(next_distance) {
+                        paths[*neighbor] += paths[node];
+                        predecessors[*neighbor].push(node);
+                    }
+                }
+            }
+
+            let mut dependency = vec![0.0; n];
+            while let Some(node) = stack.pop() {
+                for predecessor in &predecessors[node] {
+                    dependency[*predecessor] +=
+                        paths[*predecessor] / paths[node] * (1.0 + dependency[node]);
+                }
+                if node != source {
+                    scores[node] += dependency[node];
+                }
+            }
+        }
+
+        // every pair was counted from both ends
+        let pairs = if n > 2 {
+            ((n - 1) * (n - 2)) as f64
+        } else {
+            1.0
+        };
+        return scores.into_iter().map(|score| score / pairs).collect();
+    }
+
+    pub fn centrality(&self, options: &CentralityOptions) -> Vec<Centrality> {
+        // ranking a type outside the subgraph would always be empty
+        let mut within = options.within.clone();
+        if !within.contains(&options.kind) {
+            within.push(options.kind);
+        }
+        let (nodes, adjacency) = self.induced_subgraph(&within);
+        let scores = match options.metric {
+            CentralityMetric::Degree => RelationGraph::degree_scores(&adjacency),
+            CentralityMetric::PageRank => {
+                RelationGraph::pagerank_scores(&adjacency, options.damping, options.max_iterations)
+            }
+            CentralityMetric::Betweenness => RelationGraph::betweenness_scores(&adjacency),
+        };
+
+        let scores = nodes
+            .iter()
+            .zip(scores)
+            .filter(|(node_index, _)| self.g[**node_index]._node_type.kind() == options.kind)
+            .map(|(node_index, score)| (self.g[*node_index].name.to_string(), score))
+            .collect();
+        return RelationGraph::ranked(scores)
+            .into_iter()
+            .map(|each| Centrality {
+                name: each.name,
+                score: each.score,
+                rank: each.rank,
+            })
+            .collect();
+    }
+}
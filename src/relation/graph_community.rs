@@ -0,0 +1,172 @@
+use crate::relation::graph::{Community, CommunityOptions, NodeType, RelationGraph};
+use crate::relation::graph_hotness::dir_at_depth;
+use petgraph::graph::NodeIndex;
+use std::collections::{HashMap, HashSet};
+
+// shared nodes linking more files are mostly formatting or vendoring, skipped
+const MAX_CONNECTOR_FILES: usize = 100;
+
+/// community detection API
+impl RelationGraph {
+    /// file to file weights by shared `via` nodes, files in name order
+    fn cochange_projection(
+        &self,
+        options: &CommunityOptions,
+    ) -> (Vec<NodeIndex>, Vec<HashMap<usize, usize>>) {
+        let mut files: Vec<(&String, NodeIndex)> = self
+            .file_mapping
+            .iter()
+            .map(|(name, node_index)| (name.as_ref(), *node_index))
+            .collect();
+        files.sort();
+        let positions: HashMap<NodeIndex, usize> = files
+            .iter()
+            .enumerate()
+            .map(|(position, (_, node_index))| (*node_index, position))
+            .collect();
+
+        let mut weights: Vec<HashMap<usize, usize>> = vec![HashMap::new(); files.len()];
+        for connector in self.mapping(options.via).values() {
+            let members: Vec<usize> = self
+                .g
+                .neighbors(*connector)
+                .filter_map(|neighbor| positions.get(&neighbor).copied())
+                .collect();
+            if members.len() > MAX_CONNECTOR_FILES {
+                continue;
+            }
+            for (idx, file) in members.iter().enumerate() {
+                for other in &members[idx + 1..] {
+                    *weights[*file].entry(*other).or_insert(0) += 1;
+                    *weights[*other].entry(*file).or_insert(0) += 1;
+                }
+            }
+        }
+        for each in weights.iter_mut() {
+            each.retain(|_, weight| *weight >= options.min_weight.max(1));
+        }
+        return (
+            files
+                .into_iter()
+                .map(|(_, node_index)| node_index)
+                .collect(),
+            weights,
+        );
+    }
+
+    /// Label propagation in file name order, so the result is stable.
+    /// Each file takes the label with the largest link weight among its neighbors,
+    /// keeping its own on ties or else the smallest one.
+    fn propagate_labels(weights: &[HashMap<usize, usize>], max_iterations: usize) -> Vec<usize> {
+        let mut labels: Vec<usize> = (0..weights.len()).collect();
+        for _ in 0..max_iterations {
+            let mut changed = false;
+            for (file, links) in weights.iter().enumerate() {
+                if links.is_empty() {
+                    continue;
+                }
+                let mut label_weights: HashMap<usize, usize> = HashMap::new();
+                for (other, weight) in links {
+                    *label_weights.entry(labels[*other]).or_insert(0) += weight;
+                }
+                let best = *label_weights.values().max().unwrap();
+                if label_weights.get(&labels[file]) == Some(&best) {
+                    continue;
+                }
+                labels[file] = label_weights
+                    .into_iter()
+                    .filter(|(_, weight)| *weight == best)
+                    .map(|(label, _)| label)
+                    .min()
+                    .unwrap();
+                changed = true;
+            }
+            if !changed {
+                break;
+            }
+        }
+        return labels;
+    }
+
+    fn describe_community(&self, members: &[NodeIndex], top: usize) -> Community {
+        let mut issues: HashMap<String, usize> = HashMap::new();
+        let mut dirs: HashMap<String, usize> = HashMap::new();
+        let mut commits: HashSet<NodeIndex> = HashSet::new();
+        for file in members {
+            for neighbor in self.g.neighbors(*file) {
+                match self.g[neighbor]._node_type {
+                    NodeType::Issue(_) => {
+                        *issues.entry(self.g[neighbor].name.to_string()).or_insert(0) += 1;
+                    }
+                    NodeType::Commit(_) => {
+                        commits.insert(neighbor);
+                    }
+                    _ => {}
+                }
+            }
+            *dirs
+                .entry(dir_at_depth(&self.g[*file].name, usize::MAX))
+                .or_insert(0) += 1;
+        }
+
+        let mut authors: HashMap<String, usize> = HashMap::new();
+        for commit in commits {
+            for neighbor in self.g.neighbors(commit) {
+                if let NodeType::Author(_) = self.g[neighbor]._node_type {
+                    *authors
+                        .entry(self.g[neighbor].name.to_string())
+                        .or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut issues = RelationGraph::sorted_counts(issues);
+        issues.truncate(top);
+        let mut authors = RelationGraph::sorted_counts(authors);
+        authors.truncate(top);
+        let dirs = RelationGraph::sorted_counts(dirs);
+        let dir_purity = match dirs.first() {
+            Some(main_dir) => main_dir.count as f64 / members.len() as f64,
+            None => 0.0,
+        };
+        let mut files: Vec<String> = members
+            .iter()
+            .map(|file| self.g[*file].name.to_string())
+            .collect();
+        files.sort();
+        return Community {
+            id: 0,
+            files,
+            issues,
+            authors,
+            dirs,
+            dir_purity,
+        };
+    }
+
+    /// clusters of co-changed files, largest first
+    pub fn communities(&self, options: &CommunityOptions) -> Vec<Community> {
+        let (files, weights) = self.cochange_projection(options);
+        let labels = RelationGraph::propagate_labels(&weights, options.max_iterations);
+
+        let mut groups: HashMap<usize, Vec<NodeIndex>> = HashMap::new();
+        for (position, label) in labels.iter().enumerate() {
+            groups.entry(*label).or_default().push(files[position]);
+        }
+        let mut communities: Vec<Community> = groups
+            .into_values()
+            .filter(|members| members.len() >= options.min_size)
+            .map(|members| self.describe_community(&members, options.top))
+            .collect();
+        communities.sort_by(|a, b| {
+            b.files
+                .len()
+                .cmp(&a.files.len())
+                .then_with(|| a.files.cmp(&b.files))
+        });
+        for (id, community) in communities.iter_mut().enumerate() {
+            community.id = id;
+        }
+        return communities;
+    }
+}
@@ -0,0 +1,116 @@
+use crate::relation::graph::{CommitData, CommitInfo, EdgeType, NodeType, RelationGraph};
+use std::collections::HashMap;
+use std::fmt::Error;
+
+/// conventional commit API
+impl RelationGraph {
+    pub(crate) fn commit_data_mut(&mut self, commit_name: &String) -> Option<&mut CommitData> {
+        let node_index = *self.commit_mapping.get(commit_name)?;
+        self.invalidate_index();
+        if let NodeType::Commit(data) = &mut self.g[node_index]._node_type {
+            return Some(data.get_or_insert_with(CommitData::default));
+        }
+        return None;
+    }
+
+    pub(crate) fn commit_data(&self, commit_name: &String) -> Option<&CommitData> {
+        let node_index = self.commit_mapping.get(commit_name)?;
+        if let NodeType::Commit(Some(data)) = &self.g[*node_index]._node_type {
+            return Some(data);
+        }
+        return None;
+    }
+
+    pub fn commit_info(&self, commit_name: &String) -> Result<CommitInfo, Error> {
+        if !self.commit_mapping.contains_key(commit_name) {
+            return Err(Error::default());
+        }
+        return Ok(match self.commit_data(commit_name) {
+            Some(data) => CommitInfo {
+                kind: data.kind.clone(),
+                scope: data.scope.clone(),
+                breaking: data.breaking,
+                notes: data.notes.clone(),
+            },
+            None => CommitInfo::default(),
+        });
+    }
+
+    /// e.g. fix commits touching this file
+    pub fn file_related_commits_by_kind(
+        &self,
+        file_name: &String,
+        kind: &str,
+    ) -> Result<Vec<String>, Error> {
+        let commits = self.file_related_commits(file_name)?;
+        return Ok(commits
+            .into_iter()
+            .filter(|commit| {
+                return self
+                    .commit_data(commit)
+                    .and_then(|data| data.kind.as_deref())
+                    == Some(kind);
+            })
+            .collect());
+    }
+
+    /// commit count per conventional type, non conventional commits are ignored
+    pub fn file_commit_kinds(&self, file_name: &String) -> Result<HashMap<String, usize>, Error> {
+        let mut kinds: HashMap<String, usize> = HashMap::new();
+        for commit in self.file_related_commits(file_name)? {
+            if let Some(kind) = self.commit_data(&commit).and_then(|data| data.kind.clone()) {
+                *kinds.entry(kind).or_insert(0) += 1;
+            }
+        }
+        return Ok(kinds);
+    }
+
+    /// fix commits / feat commits, None if the file has no feat commit
+    pub fn file_fix_feature_ratio(&self, file_name: &String) -> Result<Option<f64>, Error> {
+        let kinds = self.file_commit_kinds(file_name)?;
+        let feat = *kinds.get("feat").unwrap_or(&0);
+        if feat == 0 {
+            return Ok(None);
+        }
+        let fix = *kinds.get("fix").unwrap_or(&0);
+        return Ok(Some(fix as f64 / feat as f64));
+    }
+
+    pub fn add_scope_node(&mut self, name: &String) {
+        return self.add_node(name, NodeType::Scope(None));
+    }
+
+    pub fn add_edge_scope2commit(&mut self, scope_name: &String, commit_name: &String) {
+        if let (Some(scope_index), Some(commit_index)) = (
+            self.scope_mapping.get(scope_name),
+            self.commit_mapping.get(commit_name),
+        ) {
+            self.add_edge(*scope_index, *commit_index, EdgeType::Scope2Commit);
+        }
+    }
+
+    pub fn add_edge_file2scope(&mut self, file_name: &String, scope_name: &String) {
+        if let (Some(file_index), Some(scope_index)) = (
+            self.file_mapping.get(file_name),
+            self.scope_mapping.get(scope_name),
+        ) {
+            self.add_edge(*file_index, *scope_index, EdgeType::File2Scope);
+        }
+    }
+
+    pub fn scopes(&self) -> Vec<String> {
+        return self.get_keys(&self.scope_mapping);
+    }
+
+    pub fn scope_related_files(&self, scope_name: &String) -> Result<Vec<String>, Error> {
+        return self.find_related(scope_name, &self.scope_mapping, &self.file_mapping);
+    }
+
+    pub fn scope_related_commits(&self, scope_name: &String) -> Result<Vec<String>, Error> {
+        return self.find_related(scope_name, &self.scope_mapping, &self.commit_mapping);
+    }
+
+    pub fn file_related_scopes(&self, file_name: &String) -> Result<Vec<String>, Error> {
+        return self.find_related(file_name, &self.file_mapping, &self.scope_mapping);
+    }
+}
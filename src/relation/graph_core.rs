@@ -1,4 +1,4 @@
-use crate::relation::graph::{EdgeType, NodeData, NodeMapping, RelationGraph};
+use crate::relation::graph::{DerivedIndex, EdgeType, NodeData, NodeMapping, RelationGraph};
 use petgraph::graph::{NodeIndex, UnGraph};
 use std::sync::Arc;
 
@@ -10,17 +10,31 @@ impl RelationGraph {
             commit_mapping: NodeMapping::new(),
             issue_mapping: NodeMapping::new(),
             author_mapping: NodeMapping::new(),
+            symbol_mapping: NodeMapping::new(),
+            scope_mapping: NodeMapping::new(),
+            person_mapping: NodeMapping::new(),
             g: UnGraph::<NodeData, EdgeType>::new_undirected(),
             conf: crate::collector::config::Config::default(),
+            complete: true,
+            last_commit: None,
+            index: DerivedIndex::default(),
         };
     }
 
+    /// drop derived metrics, every mutation must call this
+    pub(crate) fn invalidate_index(&mut self) {
+        self.index = DerivedIndex::default();
+    }
+
     pub(crate) fn add_node(&mut self, name: &String, node_type: crate::relation::graph::NodeType) {
         let mapping = match node_type {
             crate::relation::graph::NodeType::Commit(_) => &mut self.commit_mapping,
             crate::relation::graph::NodeType::File(_) => &mut self.file_mapping,
             crate::relation::graph::NodeType::Issue(_) => &mut self.issue_mapping,
             crate::relation::graph::NodeType::Author(_) => &mut self.author_mapping,
+            crate::relation::graph::NodeType::Symbol(_) => &mut self.symbol_mapping,
+            crate::relation::graph::NodeType::Scope(_) => &mut self.scope_mapping,
+            crate::relation::graph::NodeType::Person(_) => &mut self.person_mapping,
         };
 
         if !mapping.contains_key(name) {
@@ -31,6 +45,7 @@ impl RelationGraph {
             };
             let node_index = self.g.add_node(node_data);
             mapping.insert(name_rc, node_index);
+            self.invalidate_index();
         }
     }
 
@@ -56,6 +71,7 @@ impl RelationGraph {
             return;
         }
         self.g.add_edge(source_index, target_index, edge_type);
+        self.invalidate_index();
     }
 
     pub fn add_edge_file2commit(&mut self, file_name: &String, commit_name: &String) {
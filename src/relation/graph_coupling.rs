@@ -0,0 +1,94 @@
+use crate::relation::graph::{Coupling, RelationGraph};
+use std::collections::HashMap;
+use std::fmt::Error;
+
+// commits touching more files are mostly formatting or vendoring, they don't count as coupling
+const MAX_COUPLING_COMMIT_SIZE: usize = 100;
+
+/// logical coupling (co-change) API
+impl RelationGraph {
+    fn file_commit_count(&self, file_name: &String) -> usize {
+        return self
+            .file_related_commits(file_name)
+            .unwrap_or_default()
+            .len();
+    }
+
+    fn coupling(&self, file: &String, coupled: &String, support: usize) -> Coupling {
+        let file_commits = self.file_commit_count(file).max(1) as f64;
+        let coupled_commits = self.file_commit_count(coupled).max(1) as f64;
+        let total = self.commit_size().max(1) as f64;
+        return Coupling {
+            file: file.clone(),
+            coupled: coupled.clone(),
+            support,
+            confidence: support as f64 / file_commits,
+            reverse_confidence: support as f64 / coupled_commits,
+            lift: support as f64 * total / (file_commits * coupled_commits),
+        };
+    }
+
+    fn sort_couplings(couplings: &mut [Coupling]) {
+        couplings.sort_by(|a, b| {
+            b.support
+                .cmp(&a.support)
+                .then(b.confidence.total_cmp(&a.confidence))
+                .then(a.file.cmp(&b.file))
+                .then(a.coupled.cmp(&b.coupled))
+        });
+    }
+
+    /// files changed together with this file in at least `min_support` commits
+    pub fn file_coupled_files(
+        &self,
+        file_name: &String,
+        min_support: usize,
+    ) -> Result<Vec<Coupling>, Error> {
+        let mut counter: HashMap<String, usize> = HashMap::new();
+        for commit in self.file_related_commits(file_name)? {
+            let files = self.commit_related_files(&commit)?;
+            if files.len() > MAX_COUPLING_COMMIT_SIZE {
+                continue;
+            }
+            for other in files {
+                if &other != file_name {
+                    *counter.entry(other).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut couplings: Vec<Coupling> = counter
+            .into_iter()
+            .filter(|(_, support)| *support >= min_support.max(1))
+            .map(|(other, support)| self.coupling(file_name, &other, support))
+            .collect();
+        RelationGraph::sort_couplings(&mut couplings);
+        return Ok(couplings);
+    }
+
+    /// all file pairs changed together in at least `min_support` commits,
+    /// each pair once with `file` < `coupled`
+    pub fn coupling_report(&self, min_support: usize) -> Vec<Coupling> {
+        let mut counter: HashMap<(String, String), usize> = HashMap::new();
+        for commit in self.commits() {
+            let mut files = self.commit_related_files(&commit).unwrap_or_default();
+            if files.len() > MAX_COUPLING_COMMIT_SIZE {
+                continue;
+            }
+            files.sort();
+            for (idx, file) in files.iter().enumerate() {
+                for other in &files[idx + 1..] {
+                    *counter.entry((file.clone(), other.clone())).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut couplings: Vec<Coupling> = counter
+            .into_iter()
+            .filter(|(_, support)| *support >= min_support.max(1))
+            .map(|((file, other), support)| self.coupling(&file, &other, support))
+            .collect();
+        RelationGraph::sort_couplings(&mut couplings);
+        return couplings;
+    }
+}
@@ -0,0 +1,110 @@
+use crate::relation::graph::{
+    AuthorFile, GraphDiff, GraphDiffOptions, HotnessChange, NodeKind, NodeMapping, RelationGraph,
+};
+use std::collections::{HashMap, HashSet};
+
+/// snapshot comparison API
+impl RelationGraph {
+    fn sorted_keys_missing_in(mapping: &NodeMapping, other: &NodeMapping) -> Vec<String> {
+        let mut keys: Vec<String> = mapping
+            .keys()
+            .filter(|key| !other.contains_key(*key))
+            .map(|key| key.to_string())
+            .collect();
+        keys.sort();
+        return keys;
+    }
+
+    fn author_file_pairs(&self) -> HashMap<(String, String), usize> {
+        let mut pairs = HashMap::new();
+        for author in self.author_mapping.keys() {
+            for file in self
+                .traverse_scored(
+                    NodeKind::Author,
+                    author,
+                    &[NodeKind::Commit, NodeKind::File],
+                )
+                .unwrap_or_default()
+            {
+                pairs.insert((author.to_string(), file.name), file.count);
+            }
+        }
+        return pairs;
+    }
+
+    /// Compare this (older) graph with a newer one, e.g. built at a tag and at HEAD.
+    pub fn graph_diff(&self, newer: &RelationGraph, options: &GraphDiffOptions) -> GraphDiff {
+        let known_pairs: HashSet<(String, String)> = self.author_file_pairs().into_keys().collect();
+        let mut new_author_files: Vec<AuthorFile> = newer
+            .author_file_pairs()
+            .into_iter()
+            .filter(|(pair, _)| !known_pairs.contains(pair))
+            .map(|((author, file), commits)| AuthorFile {
+                author,
+                file,
+                commits,
+            })
+            .collect();
+        new_author_files.sort_by(|a, b| a.author.cmp(&b.author).then_with(|| a.file.cmp(&b.file)));
+
+        // each graph decays relative to its own newest commit
+        let before: HashMap<String, (f64, usize)> = self
+            .file_hotness(&options.model)
+            .into_iter()
+            .map(|each| (each.name, (each.score, each.rank)))
+            .collect();
+        let after: HashMap<String, (f64, usize)> = newer
+            .file_hotness(&options.model)
+            .into_iter()
+            .map(|each| (each.name, (each.score, each.rank)))
+            .collect();
+        let files: HashSet<&String> = before.keys().chain(after.keys()).collect();
+        let mut hotness_changes: Vec<HotnessChange> = files
+            .into_iter()
+            .map(|file| {
+                let before = before.get(file);
+                let after = after.get(file);
+                let before_score = before.map(|each| each.0).unwrap_or(0.0);
+                let after_score = after.map(|each| each.0).unwrap_or(0.0);
+                return HotnessChange {
+                    file: file.clone(),
+                    before: before_score,
+                    after: after_score,
+                    delta: after_score - before_score,
+                    before_rank: before.map(|each| each.1),
+                    after_rank: after.map(|each| each.1),
+                };
+            })
+            .filter(|change| change.delta.abs() >= options.min_hotness_delta)
+            .collect();
+        hotness_changes.sort_by(|a, b| {
+            b.delta
+                .abs()
+                .total_cmp(&a.delta.abs())
+                .then_with(|| a.file.cmp(&b.file))
+        });
+
+        return GraphDiff {
+            before: self.size(),
+            after: newer.size(),
+            added_files: RelationGraph::sorted_keys_missing_in(
+                &newer.file_mapping,
+                &self.file_mapping,
+            ),
+            removed_files: RelationGraph::sorted_keys_missing_in(
+                &self.file_mapping,
+                &newer.file_mapping,
+            ),
+            new_issues: RelationGraph::sorted_keys_missing_in(
+                &newer.issue_mapping,
+                &self.issue_mapping,
+            ),
+            new_authors: RelationGraph::sorted_keys_missing_in(
+                &newer.author_mapping,
+                &self.author_mapping,
+            ),
+            new_author_files,
+            hotness_changes,
+        };
+    }
+}
@@ -0,0 +1,150 @@
+use crate::relation::graph::{
+    Expert, ExpertModel, ExpertTarget, HotnessModel, NodeType, RelationGraph,
+};
+use petgraph::graph::NodeIndex;
+use std::collections::HashMap;
+use std::fmt::Error;
+
+/// expert finder API
+impl RelationGraph {
+    /// churn of a file per commit node
+    fn file_churn_by_commit(&self, file_index: NodeIndex) -> HashMap<NodeIndex, usize> {
+        let mut churn = HashMap::new();
+        if let NodeType::File(Some(data)) = &self.g[file_index]._node_type {
+            for (commit, lines) in &data.churn {
+                if let Some(commit_index) = self.commit_mapping.get(commit) {
+                    *churn.entry(*commit_index).or_insert(0) += lines;
+                }
+            }
+        }
+        return churn;
+    }
+
+    /// commits of some files, with the lines they changed in those files
+    fn files_commit_churn(&self, files: &[NodeIndex]) -> HashMap<NodeIndex, usize> {
+        let mut commits: HashMap<NodeIndex, usize> = HashMap::new();
+        for file_index in files {
+            let churn = self.file_churn_by_commit(*file_index);
+            for neighbor in self.g.neighbors(*file_index) {
+                if let NodeType::Commit(_) = self.g[neighbor]._node_type {
+                    *commits.entry(neighbor).or_insert(0) += churn.get(&neighbor).unwrap_or(&0);
+                }
+            }
+        }
+        return commits;
+    }
+
+    fn target_commit_churn(
+        &self,
+        target: &ExpertTarget,
+    ) -> Result<HashMap<NodeIndex, usize>, Error> {
+        return match target {
+            ExpertTarget::File(file_name) => match self.file_mapping.get(file_name) {
+                Some(file_index) => Ok(self.files_commit_churn(&[*file_index])),
+                None => Err(Error::default()),
+            },
+            ExpertTarget::Dir(dir) => {
+                let files = self.dir_file_indexes(dir);
+                if files.is_empty() {
+                    return Err(Error::default());
+                }
+                Ok(self.files_commit_churn(&files))
+            }
+            ExpertTarget::Issue(issue) => {
+                let issue_index = match self.issue_mapping.get(issue) {
+                    Some(issue_index) => *issue_index,
+                    None => return Err(Error::default()),
+                };
+                // whole commits, an issue has no file boundary
+                let mut commits = HashMap::new();
+                for commit in self.g.neighbors(issue_index) {
+                    if let NodeType::Commit(_) = self.g[commit]._node_type {
+                        let lines: usize = self
+                            .g
+                            .neighbors(commit)
+                            .filter(|file| matches!(self.g[*file]._node_type, NodeType::File(_)))
+                            .map(|file| *self.file_churn_by_commit(file).get(&commit).unwrap_or(&0))
+                            .sum();
+                        commits.insert(commit, lines);
+                    }
+                }
+                Ok(commits)
+            }
+        };
+    }
+
+    /// Authors ranked by `commit_weight` per commit plus `churn_weight * ln(1 + lines)`,
+    /// both decayed by commit age as in `file_hotness`.
+    pub fn experts_for(
+        &self,
+        target: &ExpertTarget,
+        model: &ExpertModel,
+    ) -> Result<Vec<Expert>, Error> {
+        let commits = self.target_commit_churn(target)?;
+        let now = model.now.or_else(|| self.newest_commit_time());
+        let decay_model = HotnessModel {
+            half_life_days: model.half_life_days,
+            ..HotnessModel::default()
+        };
+
+        let mut by_author: HashMap<NodeIndex, Vec<(NodeIndex, usize, Option<i64>)>> =
+            HashMap::new();
+        for (commit, churn) in &commits {
+            let time = match &self.g[*commit]._node_type {
+                NodeType::Commit(Some(data)) => data.time,
+                _ => None,
+            };
+            for neighbor in self.g.neighbors(*commit) {
+                if let NodeType::Author(_) = self.g[neighbor]._node_type {
+                    by_author
+                        .entry(neighbor)
+                        .or_default()
+                        .push((*commit, *churn, time));
+                }
+            }
+        }
+
+        let mut experts: Vec<Expert> = by_author
+            .into_iter()
+            .map(|(author, mut evidence)| {
+                evidence.sort_by(|a, b| {
+                    b.2.cmp(&a.2)
+                        .then_with(|| self.g[a.0].name.cmp(&self.g[b.0].name))
+                });
+                let mut score = 0.0;
+                for (_, churn, time) in &evidence {
+                    let decay = RelationGraph::decay(&decay_model, now, *time);
+                    score += model.commit_weight * decay;
+                    score += model.churn_weight * (1.0 + *churn as f64).ln() * decay;
+                }
+                let churn: usize = evidence.iter().map(|each| each.1).sum();
+                let last_time = evidence.first().and_then(|each| each.2);
+                let evidence: Vec<String> = evidence
+                    .iter()
+                    .map(|each| self.g[each.0].name.to_string())
+                    .collect();
+                let explanation = format!(
+                    "{} commits, {} changed lines, latest {}",
+                    evidence.len(),
+                    churn,
+                    evidence[0].chars().take(8).collect::<String>(),
+                );
+                return Expert {
+                    author: self.g[author].name.to_string(),
+                    score,
+                    commits: evidence.len(),
+                    churn,
+                    last_time,
+                    evidence,
+                    explanation,
+                };
+            })
+            .collect();
+        experts.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.author.cmp(&b.author))
+        });
+        return Ok(experts);
+    }
+}
@@ -1,5 +1,6 @@
-use crate::relation::graph::RelationGraph;
+use crate::relation::graph::{GraphEdge, GraphExport, GraphNode, NodeKind, RelationGraph};
 use petgraph::dot::Config;
+use petgraph::visit::EdgeRef;
 use std::collections::HashMap;
 use std::fmt::Error;
 use std::fs::File;
@@ -34,6 +35,60 @@ impl RelationGraph {
         }
     }
 
+    /// every node and edge, labelled, with a shape per node type
+    pub fn to_dot(&self) -> String {
+        let dot = petgraph::dot::Dot::with_attr_getters(
+            &self.g,
+            &[],
+            &|_, _| String::new(),
+            &|_, (_, data)| {
+                let shape = match data._node_type.kind() {
+                    NodeKind::File => "box",
+                    NodeKind::Issue => "diamond",
+                    NodeKind::Author | NodeKind::Person => "house",
+                    _ => "ellipse",
+                };
+                format!("shape = {}", shape)
+            },
+        );
+        return dot.to_string();
+    }
+
+    /// `export_dot` without the file - issue simplification, meant for small graphs like subgraphs
+    pub fn export_full_dot(&self, file_path: &str) {
+        if let Ok(mut file) = File::create(file_path) {
+            file.write_all(self.to_dot().as_bytes())
+                .expect("Failed to write to file");
+            println!("DOT representation saved to '{}'", file_path);
+        } else {
+            eprintln!("Failed to create or write to '{}'", file_path);
+        }
+    }
+
+    pub fn export_json(&self) -> GraphExport {
+        return GraphExport {
+            nodes: self
+                .g
+                .node_indices()
+                .map(|node_index| GraphNode {
+                    id: node_index.index(),
+                    kind: self.g[node_index]._node_type.kind(),
+                    name: self.g[node_index].name.to_string(),
+                })
+                .collect(),
+            edges: self
+                .g
+                .edge_references()
+                .map(|edge| GraphEdge {
+                    source: edge.source().index(),
+                    target: edge.target().index(),
+                    kind: edge.weight().to_string(),
+                    roles: edge.weight().roles().to_vec(),
+                })
+                .collect(),
+        };
+    }
+
     pub fn export_file_issue_mapping(&self) -> HashMap<String, Vec<String>> {
         let mut ret = HashMap::new();
         for (f, _) in &self.file_mapping {
@@ -1,4 +1,6 @@
-use crate::relation::graph::{EdgeType, NodeData, NodeType, RelationGraph};
+use crate::relation::graph::{
+    EdgeType, FileOwners, LineShare, NodeData, NodeKind, NodeType, RelatedCount, RelationGraph,
+};
 use std::collections::HashMap;
 use std::fmt::Error;
 
@@ -40,37 +42,192 @@ impl RelationGraph {
         return self.get_keys(&self.author_mapping);
     }
 
+    /// files an author touched, with commit counts
+    pub fn author_related_files(&self, author_name: &String) -> Result<Vec<RelatedCount>, Error> {
+        return self.traverse_scored(
+            NodeKind::Author,
+            author_name,
+            &[NodeKind::Commit, NodeKind::File],
+        );
+    }
+
+    /// issues an author worked on, with commit counts
+    pub fn author_related_issues(&self, author_name: &String) -> Result<Vec<RelatedCount>, Error> {
+        return self.traverse_scored(
+            NodeKind::Author,
+            author_name,
+            &[NodeKind::Commit, NodeKind::Issue],
+        );
+    }
+
+    pub fn file_related_authors(&self, file_name: &String) -> Result<Vec<RelatedCount>, Error> {
+        return self.traverse_scored(
+            NodeKind::File,
+            file_name,
+            &[NodeKind::Commit, NodeKind::Author],
+        );
+    }
+
+    pub fn issue_related_authors(&self, issue: &String) -> Result<Vec<RelatedCount>, Error> {
+        return self.traverse_scored(
+            NodeKind::Issue,
+            issue,
+            &[NodeKind::Commit, NodeKind::Author],
+        );
+    }
+
+    pub fn add_symbol_node(&mut self, name: &String) {
+        return self.add_node(name, NodeType::Symbol(None));
+    }
+
+    pub fn add_edge_file2symbol(&mut self, file_name: &String, symbol_name: &String) {
+        if let (Some(file_index), Some(symbol_index)) = (
+            self.file_mapping.get(file_name),
+            self.symbol_mapping.get(symbol_name),
+        ) {
+            self.add_edge(*file_index, *symbol_index, EdgeType::File2Symbol);
+        }
+    }
+
+    pub fn add_edge_symbol2commit(&mut self, symbol_name: &String, commit_name: &String) {
+        if let (Some(symbol_index), Some(commit_index)) = (
+            self.symbol_mapping.get(symbol_name),
+            self.commit_mapping.get(commit_name),
+        ) {
+            self.add_edge(*symbol_index, *commit_index, EdgeType::Symbol2Commit);
+        }
+    }
+
+    pub fn add_edge_symbol2issue(&mut self, symbol_name: &String, issue: &String) {
+        if let (Some(symbol_index), Some(issue_index)) = (
+            self.symbol_mapping.get(symbol_name),
+            self.issue_mapping.get(issue),
+        ) {
+            self.add_edge(*symbol_index, *issue_index, EdgeType::Symbol2Issue);
+        }
+    }
+
+    pub fn get_symbol_node(&self, name: &String) -> Option<&NodeData> {
+        if !self.symbol_mapping.contains_key(name) {
+            return None;
+        }
+        let node_index = self.symbol_mapping.get(name).unwrap();
+        return Some(&self.g[*node_index]);
+    }
+
+    pub fn symbols(&self) -> Vec<String> {
+        return self.get_keys(&self.symbol_mapping);
+    }
+
+    pub fn symbol_size(&self) -> usize {
+        return self.symbol_mapping.len();
+    }
+
+    pub fn file_related_symbols(&self, file_name: &String) -> Result<Vec<String>, Error> {
+        return self.find_related(file_name, &self.file_mapping, &self.symbol_mapping);
+    }
+
+    pub fn symbol_related_commits(&self, symbol_name: &String) -> Result<Vec<String>, Error> {
+        return self.find_related(symbol_name, &self.symbol_mapping, &self.commit_mapping);
+    }
+
+    pub fn symbol_related_issues(&self, symbol_name: &String) -> Result<Vec<String>, Error> {
+        return self.find_related(symbol_name, &self.symbol_mapping, &self.issue_mapping);
+    }
+
+    /// ownership from blame, lists are (name, surviving lines)
+    pub fn set_file_owners(
+        &mut self,
+        file_name: &String,
+        total_lines: usize,
+        author_lines: Vec<(String, usize)>,
+        issue_lines: Vec<(String, usize)>,
+    ) {
+        if let Some(data) = self.file_data_mut(file_name) {
+            data.total_lines = total_lines;
+            data.author_lines = author_lines;
+            data.issue_lines = issue_lines;
+        }
+    }
+
+    /// share of surviving lines per author and per issue, empty if blame disabled
+    pub fn file_owners(&self, file_name: &String) -> Result<FileOwners, Error> {
+        if !self.file_mapping.contains_key(file_name) {
+            return Err(Error::default());
+        }
+        let data = match self.file_data(file_name) {
+            Some(data) => data,
+            None => return Ok(FileOwners::default()),
+        };
+
+        let to_shares = |lines: &Vec<(String, usize)>| -> Vec<LineShare> {
+            let mut shares: Vec<LineShare> = lines
+                .iter()
+                .map(|(name, count)| LineShare {
+                    name: name.clone(),
+                    lines: *count,
+                    share: *count as f64 / data.total_lines.max(1) as f64,
+                })
+                .collect();
+            // most lines first, then by name for stable output
+            shares.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.name.cmp(&b.name)));
+            return shares;
+        };
+        return Ok(FileOwners {
+            total_lines: data.total_lines,
+            authors: to_shares(&data.author_lines),
+            issues: to_shares(&data.issue_lines),
+        });
+    }
+
     fn file_edge_counter(&self) -> HashMap<String, usize> {
         let mut edges_count_map: HashMap<_, usize> = HashMap::new();
         for (each_name, each) in &self.file_mapping {
-            let edges = self.g.edges(*each);
-            let edge_count = edges.count();
+            let edge_count = self
+                .g
+                .neighbors(*each)
+                .filter(|node_index| {
+                    matches!(
+                        self.g[*node_index]._node_type,
+                        NodeType::Commit(_) | NodeType::Issue(_)
+                    )
+                })
+                .count();
             edges_count_map.insert(each_name.to_string(), edge_count);
         }
         return edges_count_map;
     }
 
+    /// Ascending by commits + issues: a higher rank is a hotter file, equal counts share
+    /// a rank. See `file_hotness` / `hot_files` for time-decayed scores with 1 as the hottest.
     pub fn file_hot_ranks(&self) -> HashMap<String, usize> {
-        // currently we directly simply use edge counts for representing heat rates
-        // commits + issues
-        let edges_count_map = self.file_edge_counter();
-        let mut sorted_edges_count: Vec<_> = edges_count_map.into_iter().collect();
-        sorted_edges_count.sort_by(|a, b| a.1.cmp(&b.1));
-
-        // same scores might have different ranks
-        let mut ranks: HashMap<String, usize> = HashMap::new();
-        for (idx, (file_name, _)) in sorted_edges_count.iter().enumerate() {
-            ranks.insert(file_name.clone(), idx + 1);
-        }
-        return ranks;
+        return self.hot_ranks().clone();
+    }
+
+    /// computed once, until the graph changes
+    fn hot_ranks(&self) -> &HashMap<String, usize> {
+        return self.index.hot_ranks.get_or_init(|| {
+            // currently we directly simply use edge counts for representing heat rates
+            // commits + issues
+            let edges_count_map = self.file_edge_counter();
+            let mut sorted_edges_count: Vec<_> = edges_count_map.into_iter().collect();
+            sorted_edges_count.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+            let mut ranks: HashMap<String, usize> = HashMap::new();
+            let mut rank = 0;
+            for (idx, (file_name, count)) in sorted_edges_count.iter().enumerate() {
+                if idx == 0 || sorted_edges_count[idx - 1].1 != *count {
+                    rank = idx + 1;
+                }
+                ranks.insert(file_name.clone(), rank);
+            }
+            return ranks;
+        });
     }
 
+    /// see `file_hot_ranks`, 0 for unknown files
     pub fn file_hot_rank(&self, file_name: &String) -> usize {
         const DEFAULT: usize = 0;
-        if !self.file_mapping.contains_key(file_name) {
-            return DEFAULT;
-        }
-        let ranks = self.file_hot_ranks();
-        return *ranks.get(file_name).unwrap_or(&DEFAULT);
+        return *self.hot_ranks().get(file_name).unwrap_or(&DEFAULT);
     }
 }
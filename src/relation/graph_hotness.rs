@@ -0,0 +1,174 @@
+use crate::relation::graph::{HotIndex, Hotness, HotnessModel, NodeType, RelationGraph};
+use petgraph::graph::NodeIndex;
+use std::collections::HashMap;
+
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+/// parent directory of a file cut to `depth` components, "." for the root
+pub(crate) fn dir_at_depth(file_name: &str, depth: usize) -> String {
+    let mut parts: Vec<&str> = file_name.split('/').collect();
+    parts.pop();
+    parts.truncate(depth);
+    if parts.is_empty() {
+        return String::from(".");
+    }
+    return parts.join("/");
+}
+
+/// time-decayed hotness API
+impl RelationGraph {
+    pub fn set_commit_time(&mut self, commit_name: &String, time: i64) {
+        if let Some(data) = self.commit_data_mut(commit_name) {
+            data.time = Some(time);
+        }
+    }
+
+    pub fn commit_time(&self, commit_name: &String) -> Option<i64> {
+        return self.commit_data(commit_name)?.time;
+    }
+
+    /// lines added + deleted in a file by a commit
+    pub fn add_file_churn(&mut self, file_name: &String, commit_name: &String, lines: usize) {
+        let commit = match self.commit_mapping.get_key_value(commit_name) {
+            Some((commit, _)) => commit.clone(),
+            None => return,
+        };
+        if let Some(data) = self.file_data_mut(file_name) {
+            data.churn.push((commit, lines));
+        }
+    }
+
+    pub(crate) fn node_commit_time(&self, node_index: NodeIndex) -> Option<i64> {
+        if let NodeType::Commit(Some(data)) = &self.g[node_index]._node_type {
+            return data.time;
+        }
+        return None;
+    }
+
+    pub(crate) fn newest_commit_time(&self) -> Option<i64> {
+        return self
+            .commit_mapping
+            .values()
+            .filter_map(|node_index| self.node_commit_time(*node_index))
+            .max();
+    }
+
+    /// 1.0 for undated signals or without a half life
+    pub(crate) fn decay(model: &HotnessModel, now: Option<i64>, time: Option<i64>) -> f64 {
+        return match (model.half_life_days, now, time) {
+            (Some(half_life), Some(now), Some(time)) if half_life > 0.0 => {
+                let age_days = (now - time).max(0) as f64 / SECONDS_PER_DAY;
+                0.5_f64.powf(age_days / half_life)
+            }
+            _ => 1.0,
+        };
+    }
+
+    fn file_score(&self, file_index: NodeIndex, model: &HotnessModel, now: Option<i64>) -> f64 {
+        let mut score = 0.0;
+        let mut author_times: HashMap<NodeIndex, Option<i64>> = HashMap::new();
+        for neighbor in self.g.neighbors(file_index) {
+            match &self.g[neighbor]._node_type {
+                NodeType::Commit(_) => {
+                    let time = self.node_commit_time(neighbor);
+                    score += model.commit_weight * RelationGraph::decay(model, now, time);
+
+                    if model.author_weight != 0.0 {
+                        for author in self.g.neighbors(neighbor) {
+                            if let NodeType::Author(_) = self.g[author]._node_type {
+                                let newest = author_times.entry(author).or_insert(time);
+                                *newest = (*newest).max(time);
+                            }
+                        }
+                    }
+                }
+                NodeType::Issue(_) => {
+                    // an issue is as fresh as its newest commit
+                    let time = self
+                        .g
+                        .neighbors(neighbor)
+                        .filter_map(|commit| self.node_commit_time(commit))
+                        .max();
+                    score += model.issue_weight * RelationGraph::decay(model, now, time);
+                }
+                _ => {}
+            }
+        }
+        for time in author_times.values() {
+            score += model.author_weight * RelationGraph::decay(model, now, *time);
+        }
+
+        if model.churn_weight != 0.0 {
+            if let NodeType::File(Some(data)) = &self.g[file_index]._node_type {
+                for (commit, lines) in &data.churn {
+                    let time = self.commit_time(commit);
+                    let decay = RelationGraph::decay(model, now, time);
+                    score += model.churn_weight * (1.0 + *lines as f64).ln() * decay;
+                }
+            }
+        }
+        return score;
+    }
+
+    /// hottest first, ties broken by name so the order is stable
+    pub(crate) fn ranked(scores: HashMap<String, f64>) -> Vec<Hotness> {
+        let mut sorted: Vec<(String, f64)> = scores.into_iter().collect();
+        sorted.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let mut result: Vec<Hotness> = Vec::with_capacity(sorted.len());
+        for (idx, (name, score)) in sorted.into_iter().enumerate() {
+            let rank = match result.last() {
+                // same sums added in another order may differ in the last bits
+                Some(prev) if (prev.score - score).abs() <= 1e-9 * prev.score.abs().max(1.0) => {
+                    prev.rank
+                }
+                _ => idx + 1,
+            };
+            result.push(Hotness { name, score, rank });
+        }
+        return result;
+    }
+
+    pub fn file_hotness(&self, model: &HotnessModel) -> Vec<Hotness> {
+        let now = model.now.or_else(|| self.newest_commit_time());
+        let scores = self
+            .file_mapping
+            .iter()
+            .map(|(name, node_index)| (name.to_string(), self.file_score(*node_index, model, now)))
+            .collect();
+        return RelationGraph::ranked(scores);
+    }
+
+    /// file scores summed per directory, see `dir_at_depth`
+    pub fn dir_hotness(&self, model: &HotnessModel, depth: usize) -> Vec<Hotness> {
+        let mut scores: HashMap<String, f64> = HashMap::new();
+        for file in self.file_hotness(model) {
+            *scores.entry(dir_at_depth(&file.name, depth)).or_insert(0.0) += file.score;
+        }
+        return RelationGraph::ranked(scores);
+    }
+
+    fn hot_index(&self) -> &HotIndex {
+        return self.index.hotness.get_or_init(|| {
+            let files = self.file_hotness(&HotnessModel::default());
+            let positions = files
+                .iter()
+                .enumerate()
+                .map(|(idx, each)| (each.name.clone(), idx))
+                .collect();
+            return HotIndex { files, positions };
+        });
+    }
+
+    /// hottest files by the default model, cached until the graph changes
+    pub fn hot_files(&self, top: usize) -> Vec<Hotness> {
+        return self.hot_index().files.iter().take(top).cloned().collect();
+    }
+
+    /// single file lookup in the cached default model
+    pub fn file_hot(&self, file_name: &String) -> Option<Hotness> {
+        let index = self.hot_index();
+        let position = index.positions.get(file_name)?;
+        return Some(index.files[*position].clone());
+    }
+}
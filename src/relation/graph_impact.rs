@@ -0,0 +1,63 @@
+use crate::relation::graph::{ChangeImpact, RelatedCount, RelationGraph};
+use std::collections::HashMap;
+
+/// change set API, answers questions about changes not in the graph
+impl RelationGraph {
+    pub(crate) fn sorted_counts(counter: HashMap<String, usize>) -> Vec<RelatedCount> {
+        let mut counts: Vec<RelatedCount> = counter
+            .into_iter()
+            .map(|(name, count)| RelatedCount { name, count })
+            .collect();
+        // most related first, then by name for stable output
+        counts.sort_by(|a, b| b.count.cmp(&a.count).then(a.name.cmp(&b.name)));
+        return counts;
+    }
+
+    /// related issues, commits, authors and co-changed files of a change set,
+    /// without adding it to the graph
+    pub fn change_impact(&self, files: &[String]) -> ChangeImpact {
+        let mut unknown_files = Vec::new();
+        let mut commits: Vec<String> = Vec::new();
+        let mut issues: HashMap<String, usize> = HashMap::new();
+        let mut authors: HashMap<String, usize> = HashMap::new();
+        let mut cochanged: HashMap<String, usize> = HashMap::new();
+
+        for file in files {
+            let file_commits = match self.file_related_commits(file) {
+                Ok(file_commits) => file_commits,
+                Err(_) => {
+                    unknown_files.push(file.clone());
+                    continue;
+                }
+            };
+            for issue in self.file_related_issues(file).unwrap_or_default() {
+                *issues.entry(issue).or_insert(0) += 1;
+            }
+            for commit in file_commits {
+                if !commits.contains(&commit) {
+                    commits.push(commit);
+                }
+            }
+        }
+
+        for commit in &commits {
+            for author in self.commit_related_authors(commit).unwrap_or_default() {
+                *authors.entry(author).or_insert(0) += 1;
+            }
+            for other in self.commit_related_files(commit).unwrap_or_default() {
+                if !files.contains(&other) {
+                    *cochanged.entry(other).or_insert(0) += 1;
+                }
+            }
+        }
+
+        return ChangeImpact {
+            files: files.to_vec(),
+            unknown_files,
+            issues: RelationGraph::sorted_counts(issues),
+            commits,
+            authors: RelationGraph::sorted_counts(authors),
+            cochanged_files: RelationGraph::sorted_counts(cochanged),
+        };
+    }
+}
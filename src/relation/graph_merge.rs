@@ -0,0 +1,263 @@
+use crate::relation::graph::{
+    CommitData, EdgeType, FileData, MergeConflict, MergeReport, NodeKind, NodeType, RelationGraph,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+fn empty_node_type(kind: NodeKind) -> NodeType {
+    return match kind {
+        NodeKind::File => NodeType::File(None),
+        NodeKind::Commit => NodeType::Commit(None),
+        NodeKind::Issue => NodeType::Issue(None),
+        NodeKind::Author => NodeType::Author(None),
+        NodeKind::Symbol => NodeType::Symbol(None),
+        NodeKind::Scope => NodeType::Scope(None),
+        NodeKind::Person => NodeType::Person(None),
+    };
+}
+
+fn conflict<T: ToString>(
+    kind: Option<NodeKind>,
+    name: &str,
+    field: &str,
+    ours: T,
+    theirs: T,
+) -> MergeConflict {
+    return MergeConflict {
+        kind,
+        name: name.to_string(),
+        field: field.to_string(),
+        ours: ours.to_string(),
+        theirs: theirs.to_string(),
+    };
+}
+
+/// e.g. `alice <alice@example.com>: 3, bob <bob@example.com>: 1`
+fn blame_summary(lines: &[(String, usize)]) -> String {
+    return lines
+        .iter()
+        .map(|(name, count)| format!("{}: {}", name, count))
+        .collect::<Vec<String>>()
+        .join(", ");
+}
+
+/// keep ours, fill it from theirs, report if both are set and differ
+fn merge_option<T: Clone + PartialEq + ToString>(
+    ours: &mut Option<T>,
+    theirs: &Option<T>,
+    name: &str,
+    field: &str,
+    conflicts: &mut Vec<MergeConflict>,
+) {
+    match (ours.as_ref(), theirs) {
+        (None, Some(value)) => *ours = Some(value.clone()),
+        (Some(a), Some(b)) if a != b => conflicts.push(conflict(
+            Some(NodeKind::Commit),
+            name,
+            field,
+            a.to_string(),
+            b.to_string(),
+        )),
+        _ => {}
+    }
+}
+
+/// graph combination API
+impl RelationGraph {
+    /// commit names shared with our commit nodes, like `add_file_range` does
+    fn own_commit_name(&self, commit_name: &Arc<String>) -> Arc<String> {
+        return match self.commit_mapping.get_key_value(commit_name) {
+            Some((commit, _)) => commit.clone(),
+            None => commit_name.clone(),
+        };
+    }
+
+    fn merge_file_data(
+        &mut self,
+        file_name: &String,
+        theirs: &FileData,
+        conflicts: &mut Vec<MergeConflict>,
+    ) {
+        let mut theirs = theirs.clone();
+        for range in &mut theirs.line_ranges {
+            range.commit = self.own_commit_name(&range.commit);
+        }
+        for (commit, _) in &mut theirs.churn {
+            *commit = self.own_commit_name(commit);
+        }
+        let ours = match self.file_data_mut(file_name) {
+            Some(ours) => ours,
+            None => return,
+        };
+
+        // blame is a snapshot at one HEAD, it can't be added up
+        let blamed = |data: &FileData| data.total_lines > 0 || !data.author_lines.is_empty();
+        if !blamed(ours) {
+            ours.total_lines = theirs.total_lines;
+            ours.author_lines = theirs.author_lines;
+            ours.issue_lines = theirs.issue_lines;
+        } else if blamed(&theirs) {
+            if ours.total_lines != theirs.total_lines {
+                conflicts.push(conflict(
+                    Some(NodeKind::File),
+                    file_name,
+                    "total_lines",
+                    ours.total_lines,
+                    theirs.total_lines,
+                ));
+            }
+            let lines = [
+                ("author_lines", &ours.author_lines, &theirs.author_lines),
+                ("issue_lines", &ours.issue_lines, &theirs.issue_lines),
+            ];
+            for (field, ours, theirs) in lines {
+                if ours != theirs {
+                    conflicts.push(conflict(
+                        Some(NodeKind::File),
+                        file_name,
+                        field,
+                        blame_summary(ours),
+                        blame_summary(theirs),
+                    ));
+                }
+            }
+        }
+
+        let range_commits: HashSet<Arc<String>> = ours
+            .line_ranges
+            .iter()
+            .map(|range| range.commit.clone())
+            .collect();
+        ours.line_ranges.extend(
+            theirs
+                .line_ranges
+                .into_iter()
+                .filter(|range| !range_commits.contains(&range.commit)),
+        );
+        let churn_commits: HashSet<Arc<String>> = ours
+            .churn
+            .iter()
+            .map(|(commit, _)| commit.clone())
+            .collect();
+        ours.churn.extend(
+            theirs
+                .churn
+                .into_iter()
+                .filter(|(commit, _)| !churn_commits.contains(commit)),
+        );
+    }
+
+    fn merge_commit_data(
+        &mut self,
+        commit_name: &String,
+        theirs: &CommitData,
+        conflicts: &mut Vec<MergeConflict>,
+    ) {
+        let ours = match self.commit_data_mut(commit_name) {
+            Some(ours) => ours,
+            None => return,
+        };
+        merge_option(&mut ours.kind, &theirs.kind, commit_name, "kind", conflicts);
+        merge_option(
+            &mut ours.scope,
+            &theirs.scope,
+            commit_name,
+            "scope",
+            conflicts,
+        );
+        merge_option(&mut ours.time, &theirs.time, commit_name, "time", conflicts);
+        ours.breaking |= theirs.breaking;
+        for note in &theirs.notes {
+            if !ours.notes.contains(note) {
+                ours.notes.push(note.clone());
+            }
+        }
+    }
+
+    fn merge_config(&self, other: &RelationGraph, conflicts: &mut Vec<MergeConflict>) {
+        let ours = serde_json::to_value(&self.conf).unwrap_or_default();
+        let theirs = serde_json::to_value(&other.conf).unwrap_or_default();
+        if let (Some(ours), Some(theirs)) = (ours.as_object(), theirs.as_object()) {
+            for (field, value) in ours {
+                let other_value = theirs.get(field).cloned().unwrap_or_default();
+                if *value != other_value {
+                    conflicts.push(conflict(
+                        None,
+                        "config",
+                        field,
+                        value.to_string(),
+                        other_value.to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Add the nodes, edges and node data of another graph, e.g. built from another
+    /// repository or time slice. Where both know a value differently ours is kept
+    /// and the difference reported.
+    pub fn merge(&mut self, other: &RelationGraph) -> MergeReport {
+        let mut report = MergeReport::default();
+        self.merge_config(other, &mut report.conflicts);
+        match (&self.last_commit, &other.last_commit) {
+            (None, Some(theirs)) => self.last_commit = Some(theirs.clone()),
+            (Some(ours), Some(theirs)) if ours != theirs => {
+                report
+                    .conflicts
+                    .push(conflict(None, "graph", "last_commit", ours, theirs))
+            }
+            _ => {}
+        }
+        self.complete &= other.complete;
+
+        // commits first, so file data can share their names
+        let node_count = self.g.node_count();
+        let mut nodes: Vec<_> = other.g.node_indices().collect();
+        nodes.sort_by_key(|node_index| other.g[*node_index]._node_type.kind() != NodeKind::Commit);
+        for node_index in &nodes {
+            let data = &other.g[*node_index];
+            self.add_node(&data.name, empty_node_type(data._node_type.kind()));
+        }
+        report.added_nodes = self.g.node_count() - node_count;
+
+        for node_index in &nodes {
+            let data = &other.g[*node_index];
+            match &data._node_type {
+                NodeType::File(Some(theirs)) => {
+                    self.merge_file_data(&data.name, theirs, &mut report.conflicts)
+                }
+                NodeType::Commit(Some(theirs)) => {
+                    self.merge_commit_data(&data.name, theirs, &mut report.conflicts)
+                }
+                _ => {}
+            }
+        }
+
+        let edge_count = self.g.edge_count();
+        for edge in other.g.edge_indices() {
+            let (source, target) = match other.g.edge_endpoints(edge) {
+                Some(endpoints) => endpoints,
+                None => continue,
+            };
+            let (source, target) = (&other.g[source], &other.g[target]);
+            if let (Some(source_index), Some(target_index)) = (
+                self.mapping(source._node_type.kind()).get(&source.name),
+                self.mapping(target._node_type.kind()).get(&target.name),
+            ) {
+                let (source_index, target_index) = (*source_index, *target_index);
+                match &other.g[edge] {
+                    // roles add up instead of the first edge winning
+                    EdgeType::Person2Commit(roles) => {
+                        for role in roles {
+                            self.add_person_role(source_index, target_index, role);
+                        }
+                    }
+                    edge_type => self.add_edge(source_index, target_index, edge_type.clone()),
+                }
+            }
+        }
+        report.added_edges = self.g.edge_count() - edge_count;
+        self.invalidate_index();
+        return report;
+    }
+}
@@ -1,4 +1,4 @@
-use crate::relation::graph::{GraphSize, NodeData, NodeMapping, RelationGraph};
+use crate::relation::graph::{GraphSize, NodeData, NodeKind, NodeMapping, RelationGraph};
 use std::fmt::Error;
 
 /// query API
@@ -27,6 +27,30 @@ impl RelationGraph {
         return Some(&self.g[*node_index]);
     }
 
+    pub(crate) fn mapping(&self, kind: NodeKind) -> &NodeMapping {
+        return match kind {
+            NodeKind::File => &self.file_mapping,
+            NodeKind::Commit => &self.commit_mapping,
+            NodeKind::Issue => &self.issue_mapping,
+            NodeKind::Author => &self.author_mapping,
+            NodeKind::Symbol => &self.symbol_mapping,
+            NodeKind::Scope => &self.scope_mapping,
+            NodeKind::Person => &self.person_mapping,
+        };
+    }
+
+    pub(crate) fn mapping_mut(&mut self, kind: NodeKind) -> &mut NodeMapping {
+        return match kind {
+            NodeKind::File => &mut self.file_mapping,
+            NodeKind::Commit => &mut self.commit_mapping,
+            NodeKind::Issue => &mut self.issue_mapping,
+            NodeKind::Author => &mut self.author_mapping,
+            NodeKind::Symbol => &mut self.symbol_mapping,
+            NodeKind::Scope => &mut self.scope_mapping,
+            NodeKind::Person => &mut self.person_mapping,
+        };
+    }
+
     pub(crate) fn get_keys(&self, node_mapping: &NodeMapping) -> Vec<String> {
         return node_mapping
             .keys()
@@ -59,8 +83,9 @@ impl RelationGraph {
             .g
             .neighbors(src[entry])
             .filter(|node_index| {
+                // persons and authors share the `name <email>` format, match the node itself
                 let data = &self.g[*node_index];
-                return target.contains_key(&data.name);
+                return target.get(&data.name) == Some(node_index);
             })
             .map(|node_index| {
                 return self.g[node_index].name.to_string().clone();
@@ -106,6 +131,16 @@ impl RelationGraph {
         return self.issue_mapping.len();
     }
 
+    /// false if the walk stopped early because of cancellation or time budget
+    pub fn is_complete(&self) -> bool {
+        return self.complete;
+    }
+
+    /// the oldest commit processed by the walk
+    pub fn last_commit(&self) -> Option<&String> {
+        return self.last_commit.as_ref();
+    }
+
     pub fn size(&self) -> GraphSize {
         return GraphSize {
             file_size: self.file_size(),
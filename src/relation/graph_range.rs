@@ -0,0 +1,104 @@
+use crate::relation::graph::{FileData, LineRange, NodeType, RelationGraph};
+use std::fmt::Error;
+use std::sync::Arc;
+
+/// line range API, like an issue-aware blame
+impl RelationGraph {
+    pub(crate) fn file_data_mut(&mut self, file_name: &String) -> Option<&mut FileData> {
+        let node_index = *self.file_mapping.get(file_name)?;
+        self.invalidate_index();
+        if let NodeType::File(data) = &mut self.g[node_index]._node_type {
+            return Some(data.get_or_insert_with(FileData::default));
+        }
+        return None;
+    }
+
+    pub(crate) fn file_data(&self, file_name: &String) -> Option<&FileData> {
+        let node_index = self.file_mapping.get(file_name)?;
+        if let NodeType::File(Some(data)) = &self.g[*node_index]._node_type {
+            return Some(data);
+        }
+        return None;
+    }
+
+    /// lines `start..=end` (HEAD numbering) of a file were last touched by a commit
+    pub fn add_file_range(
+        &mut self,
+        file_name: &String,
+        commit_name: &String,
+        start: u32,
+        end: u32,
+    ) {
+        let commit = match self.commit_mapping.get_key_value(commit_name) {
+            Some((commit, _)) => commit.clone(),
+            None => return,
+        };
+        if let Some(data) = self.file_data_mut(file_name) {
+            data.line_ranges.push(LineRange { commit, start, end });
+        }
+    }
+
+    fn file_range_commits(
+        &self,
+        file_name: &String,
+        start: u32,
+        end: u32,
+    ) -> Result<Vec<Arc<String>>, Error> {
+        if !self.file_mapping.contains_key(file_name) {
+            return Err(Error::default());
+        }
+        let mut commits: Vec<Arc<String>> = Vec::new();
+        if let Some(data) = self.file_data(file_name) {
+            for range in &data.line_ranges {
+                if range.start <= end && start <= range.end && !commits.contains(&range.commit) {
+                    commits.push(range.commit.clone());
+                }
+            }
+        }
+        return Ok(commits);
+    }
+
+    pub fn file_range_related_commits(
+        &self,
+        file_name: &String,
+        start: u32,
+        end: u32,
+    ) -> Result<Vec<String>, Error> {
+        let commits = self.file_range_commits(file_name, start, end)?;
+        return Ok(commits.iter().map(|each| each.to_string()).collect());
+    }
+
+    pub fn file_range_related_issues(
+        &self,
+        file_name: &String,
+        start: u32,
+        end: u32,
+    ) -> Result<Vec<String>, Error> {
+        let mut issues: Vec<String> = Vec::new();
+        for commit in self.file_range_commits(file_name, start, end)? {
+            for issue in self.commit_related_issues(&commit)? {
+                if !issues.contains(&issue) {
+                    issues.push(issue);
+                }
+            }
+        }
+        return Ok(issues);
+    }
+
+    pub fn file_range_related_authors(
+        &self,
+        file_name: &String,
+        start: u32,
+        end: u32,
+    ) -> Result<Vec<String>, Error> {
+        let mut authors: Vec<String> = Vec::new();
+        for commit in self.file_range_commits(file_name, start, end)? {
+            for author in self.commit_related_authors(&commit)? {
+                if !authors.contains(&author) {
+                    authors.push(author);
+                }
+            }
+        }
+        return Ok(authors);
+    }
+}
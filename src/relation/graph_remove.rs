@@ -0,0 +1,191 @@
+use crate::relation::graph::{
+    EdgeType, NodeKind, NodeType, PrunePolicy, PruneReport, RelationGraph,
+};
+use petgraph::graph::NodeIndex;
+use regex::Regex;
+use std::fmt::Error;
+
+/// removal API
+impl RelationGraph {
+    /// Edges like File2Issue or File2Symbol only exist because of commits linking both ends.
+    /// Drop the ones no other commit than `commit_index` supports.
+    fn remove_derived_edges(&mut self, commit_index: NodeIndex) {
+        let neighbors: Vec<NodeIndex> = self.g.neighbors(commit_index).collect();
+        let mut unsupported = Vec::new();
+        for source in &neighbors {
+            for target in &neighbors {
+                let edge = match self.g.find_edge(*source, *target) {
+                    Some(edge) => edge,
+                    None => continue,
+                };
+                let derived = matches!(
+                    (&self.g[edge], self.g[*source]._node_type.kind()),
+                    (EdgeType::File2Issue, NodeKind::File)
+                        | (EdgeType::File2Symbol, NodeKind::File)
+                        | (EdgeType::Symbol2Issue, NodeKind::Symbol)
+                        | (EdgeType::File2Scope, NodeKind::File)
+                );
+                if !derived {
+                    continue;
+                }
+                let supported = self.g.neighbors(*source).any(|other| {
+                    other != commit_index
+                        && matches!(self.g[other]._node_type, NodeType::Commit(_))
+                        && self.g.find_edge(other, *target).is_some()
+                });
+                if !supported {
+                    unsupported.push(edge);
+                }
+            }
+        }
+        // edge indexes shift on removal too, highest first keeps the rest valid
+        unsupported.sort();
+        unsupported.dedup();
+        for edge in unsupported.into_iter().rev() {
+            self.g.remove_edge(edge);
+        }
+    }
+
+    /// blame and range data refer to other nodes by name
+    fn remove_file_data_references(&mut self, kind: NodeKind, name: &String) {
+        let files: Vec<NodeIndex> = self.file_mapping.values().copied().collect();
+        for file_index in files {
+            if let NodeType::File(Some(data)) = &mut self.g[file_index]._node_type {
+                match kind {
+                    NodeKind::Commit => {
+                        data.line_ranges
+                            .retain(|range| range.commit.as_ref() != name);
+                        data.churn.retain(|(commit, _)| commit.as_ref() != name);
+                    }
+                    NodeKind::Author => data.author_lines.retain(|(author, _)| author != name),
+                    NodeKind::Issue => data.issue_lines.retain(|(issue, _)| issue != name),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Remove a node with its edges. false if there is no such node.
+    pub fn remove_node(&mut self, kind: NodeKind, name: &String) -> bool {
+        let node_index = match self.mapping_mut(kind).remove(name) {
+            Some(node_index) => node_index,
+            None => return false,
+        };
+        if kind == NodeKind::Commit {
+            self.remove_derived_edges(node_index);
+            if self.last_commit.as_ref() == Some(name) {
+                self.last_commit = None;
+            }
+        }
+        self.remove_file_data_references(kind, name);
+
+        // petgraph moves the last node into the freed index
+        let last_index = NodeIndex::new(self.g.node_count() - 1);
+        self.g.remove_node(node_index);
+        if last_index != node_index {
+            let moved = &self.g[node_index];
+            let moved_kind = moved._node_type.kind();
+            let moved_name = moved.name.clone();
+            self.mapping_mut(moved_kind).insert(moved_name, node_index);
+        }
+        self.invalidate_index();
+        return true;
+    }
+
+    pub fn remove_edge(
+        &mut self,
+        source_kind: NodeKind,
+        source_name: &String,
+        target_kind: NodeKind,
+        target_name: &String,
+    ) -> bool {
+        let edge = match (
+            self.mapping(source_kind).get(source_name),
+            self.mapping(target_kind).get(target_name),
+        ) {
+            (Some(source), Some(target)) => self.g.find_edge(*source, *target),
+            _ => None,
+        };
+        return match edge {
+            Some(edge) => {
+                self.g.remove_edge(edge);
+                self.invalidate_index();
+                true
+            }
+            None => false,
+        };
+    }
+
+    fn commit_file_count(&self, commit_index: NodeIndex) -> usize {
+        return self
+            .g
+            .neighbors(commit_index)
+            .filter(|neighbor| matches!(self.g[*neighbor]._node_type, NodeType::File(_)))
+            .count();
+    }
+
+    /// Remove old or oversized commits and unwanted authors, e.g. to keep a long running
+    /// server small. Err if `author_regex` is invalid.
+    pub fn prune(&mut self, policy: &PrunePolicy) -> Result<PruneReport, Error> {
+        let author_regex = match &policy.author_regex {
+            Some(pattern) => Some(Regex::new(pattern).map_err(|_| Error::default())?),
+            None => None,
+        };
+        let authors: Vec<String> = match &author_regex {
+            Some(regex) => self
+                .author_mapping
+                .keys()
+                .filter(|author| regex.is_match(author))
+                .map(|author| author.to_string())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let mut commits: Vec<String> = Vec::new();
+        for (commit, commit_index) in &self.commit_mapping {
+            let too_old = match (policy.before, self.node_commit_time(*commit_index)) {
+                (Some(before), Some(time)) => time < before,
+                _ => false,
+            };
+            let too_large = policy
+                .max_commit_files
+                .is_some_and(|max| self.commit_file_count(*commit_index) > max);
+            let by_author = self.g.neighbors(*commit_index).any(|neighbor| {
+                matches!(self.g[neighbor]._node_type, NodeType::Author(_))
+                    && authors.contains(&self.g[neighbor].name)
+            });
+            if too_old || too_large || by_author {
+                commits.push(commit.to_string());
+            }
+        }
+
+        let mut report = PruneReport::default();
+        for commit in &commits {
+            if self.remove_node(NodeKind::Commit, commit) {
+                report.commits += 1;
+            }
+        }
+        for author in &authors {
+            if self.remove_node(NodeKind::Author, author) {
+                report.authors += 1;
+            }
+        }
+        if policy.orphans {
+            let orphans: Vec<(NodeKind, String)> = self
+                .g
+                .node_indices()
+                .filter(|node_index| self.g.neighbors(*node_index).next().is_none())
+                .map(|node_index| {
+                    let data = &self.g[node_index];
+                    (data._node_type.kind(), data.name.to_string())
+                })
+                .collect();
+            for (kind, name) in &orphans {
+                if self.remove_node(*kind, name) {
+                    report.orphans += 1;
+                }
+            }
+        }
+        return Ok(report);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::relation::graph::{NodeKind, RelationGraph, SubgraphOptions};
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Error;
+
+/// ego subgraph API
+impl RelationGraph {
+    /// The k-hop neighborhood of a node as a standalone graph, node data included.
+    /// Only nodes of `options.kinds` are kept and walked through.
+    pub fn subgraph(
+        &self,
+        kind: NodeKind,
+        name: &String,
+        options: &SubgraphOptions,
+    ) -> Result<RelationGraph, Error> {
+        let center = match self.mapping(kind).get(name) {
+            Some(center) => *center,
+            None => return Err(Error::default()),
+        };
+
+        // bfs, so the nearest nodes survive the size cap
+        let mut kept: Vec<NodeIndex> = vec![center];
+        let mut hops: HashMap<NodeIndex, usize> = HashMap::from([(center, 0)]);
+        let mut queue = VecDeque::from([center]);
+        'walk: while let Some(node_index) = queue.pop_front() {
+            if hops[&node_index] >= options.hops {
+                continue;
+            }
+            for neighbor in self.g.neighbors(node_index) {
+                if hops.contains_key(&neighbor) {
+                    continue;
+                }
+                let neighbor_kind = self.g[neighbor]._node_type.kind();
+                if !options.kinds.is_empty() && !options.kinds.contains(&neighbor_kind) {
+                    continue;
+                }
+                if kept.len() >= options.max_nodes.max(1) {
+                    break 'walk;
+                }
+                hops.insert(neighbor, hops[&node_index] + 1);
+                kept.push(neighbor);
+                queue.push_back(neighbor);
+            }
+        }
+
+        let mut graph = RelationGraph::new();
+        graph.conf = self.conf.clone();
+        graph.complete = self.complete;
+        let mut indexes: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+        for node_index in &kept {
+            let data = &self.g[*node_index];
+            graph.add_node(&data.name, data._node_type.clone());
+            let new_index = graph.mapping(data._node_type.kind())[&data.name];
+            indexes.insert(*node_index, new_index);
+        }
+        for node_index in &kept {
+            for edge in self.g.edges(*node_index) {
+                if let (Some(source), Some(target)) =
+                    (indexes.get(&edge.source()), indexes.get(&edge.target()))
+                {
+                    // seen from both ends, add_edge skips the second one
+                    graph.add_edge(*source, *target, edge.weight().clone());
+                }
+            }
+        }
+        return Ok(graph);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::relation::graph::{EdgeType, NodeType, PersonRole, RelationGraph};
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Error;
+
+/// trailer (reviewer, sign-off ...) API
+impl RelationGraph {
+    pub fn add_person_node(&mut self, name: &String) {
+        return self.add_node(name, NodeType::Person(None));
+    }
+
+    pub fn add_edge_person2commit(
+        &mut self,
+        person_name: &String,
+        commit_name: &String,
+        role: &str,
+    ) {
+        if let (Some(person_index), Some(commit_index)) = (
+            self.person_mapping.get(person_name),
+            self.commit_mapping.get(commit_name),
+        ) {
+            self.add_person_role(*person_index, *commit_index, role);
+        }
+    }
+
+    /// one edge per person and commit, further roles join it
+    pub(crate) fn add_person_role(
+        &mut self,
+        person_index: NodeIndex,
+        commit_index: NodeIndex,
+        role: &str,
+    ) {
+        if let Some(edge) = self.g.find_edge(person_index, commit_index) {
+            if let EdgeType::Person2Commit(roles) = &mut self.g[edge] {
+                if !roles.iter().any(|each| each == role) {
+                    roles.push(role.to_string());
+                    self.invalidate_index();
+                }
+            }
+            return;
+        }
+        self.add_edge(
+            person_index,
+            commit_index,
+            EdgeType::Person2Commit(vec![role.to_string()]),
+        );
+    }
+
+    pub fn persons(&self) -> Vec<String> {
+        return self.get_keys(&self.person_mapping);
+    }
+
+    pub fn person_related_commits(&self, person_name: &String) -> Result<Vec<String>, Error> {
+        return self.find_related(person_name, &self.person_mapping, &self.commit_mapping);
+    }
+
+    fn commit_persons(&self, commit_index: NodeIndex) -> Vec<PersonRole> {
+        return self
+            .g
+            .edges(commit_index)
+            .flat_map(|edge| {
+                let roles: &[String] = match edge.weight() {
+                    EdgeType::Person2Commit(roles) => roles,
+                    _ => &[],
+                };
+                let other = if edge.source() == commit_index {
+                    edge.target()
+                } else {
+                    edge.source()
+                };
+                return roles.iter().map(move |role| PersonRole {
+                    person: self.g[other].name.to_string(),
+                    role: role.clone(),
+                });
+            })
+            .collect();
+    }
+
+    pub fn commit_related_persons(&self, commit_name: &String) -> Result<Vec<PersonRole>, Error> {
+        return match self.commit_mapping.get(commit_name) {
+            Some(commit_index) => Ok(self.commit_persons(*commit_index)),
+            None => Err(Error::default()),
+        };
+    }
+
+    fn count_persons(
+        &self,
+        commits: impl Iterator<Item = NodeIndex>,
+        role: Option<&str>,
+    ) -> HashMap<String, usize> {
+        let mut counter: HashMap<String, usize> = HashMap::new();
+        for commit_index in commits {
+            for each in self.commit_persons(commit_index) {
+                if role.is_none_or(|r| r.eq_ignore_ascii_case(&each.role)) {
+                    *counter.entry(each.person).or_insert(0) += 1;
+                }
+            }
+        }
+        return counter;
+    }
+
+    /// person -> commits on this file, optionally only one role
+    pub fn file_related_reviewers(
+        &self,
+        file_name: &String,
+        role: Option<&str>,
+    ) -> Result<HashMap<String, usize>, Error> {
+        let file_index = *self.file_mapping.get(file_name).ok_or(Error::default())?;
+        return Ok(self.count_persons(self.file_commit_indexes(file_index).into_iter(), role));
+    }
+
+    /// like `file_related_reviewers`, for all files under a directory
+    pub fn dir_related_reviewers(
+        &self,
+        dir: &String,
+        role: Option<&str>,
+    ) -> HashMap<String, usize> {
+        let mut commits: HashSet<NodeIndex> = HashSet::new();
+        for file_index in self.dir_file_indexes(dir) {
+            commits.extend(self.file_commit_indexes(file_index));
+        }
+        return self.count_persons(commits.into_iter(), role);
+    }
+
+    /// person -> commits carrying their trailer, for reviewer load reports
+    pub fn reviewer_load(&self, role: Option<&str>) -> HashMap<String, usize> {
+        return self.count_persons(self.commit_mapping.values().copied(), role);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::relation::graph::{NodeKind, NodePath, PathNode, RelatedCount, RelationGraph};
+use petgraph::graph::NodeIndex;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Error;
+
+/// multi-hop traversal API
+impl RelationGraph {
+    /// Follow `path` (e.g. Issue -> File) from a start node, counting how many
+    /// paths reach each node. The start node itself is not part of the result.
+    pub fn traverse_scored(
+        &self,
+        start_kind: NodeKind,
+        start_name: &String,
+        path: &[NodeKind],
+    ) -> Result<Vec<RelatedCount>, Error> {
+        let start_index = match self.mapping(start_kind).get(start_name) {
+            Some(start_index) => *start_index,
+            None => return Err(Error::default()),
+        };
+
+        let mut frontier: HashMap<NodeIndex, usize> = HashMap::from([(start_index, 1)]);
+        for kind in path {
+            let mut next: HashMap<NodeIndex, usize> = HashMap::new();
+            for (node_index, count) in &frontier {
+                for neighbor in self.g.neighbors(*node_index) {
+                    if self.g[neighbor]._node_type.kind() == *kind {
+                        // path counts multiply per hop, long paths can overflow
+                        let total = next.entry(neighbor).or_insert(0);
+                        *total = total.saturating_add(*count);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        frontier.remove(&start_index);
+
+        let counter = frontier
+            .into_iter()
+            .map(|(node_index, count)| (self.g[node_index].name.to_string(), count))
+            .collect();
+        return Ok(RelationGraph::sorted_counts(counter));
+    }
+
+    /// `traverse_scored` without scores, most related first
+    pub fn traverse(
+        &self,
+        start_kind: NodeKind,
+        start_name: &String,
+        path: &[NodeKind],
+    ) -> Result<Vec<String>, Error> {
+        let scored = self.traverse_scored(start_kind, start_name, path)?;
+        return Ok(scored.into_iter().map(|each| each.name).collect());
+    }
+
+    pub(crate) fn path_node(&self, node_index: NodeIndex) -> PathNode {
+        let data = &self.g[node_index];
+        return PathNode {
+            kind: data._node_type.kind(),
+            name: data.name.to_string(),
+        };
+    }
+
+    /// Shortest chain between two nodes (e.g. file -> commit -> issue), within `max_hops` edges.
+    /// Ok(None) if they are not connected that closely.
+    pub fn shortest_path(
+        &self,
+        from_kind: NodeKind,
+        from_name: &String,
+        to_kind: NodeKind,
+        to_name: &String,
+        max_hops: usize,
+    ) -> Result<Option<NodePath>, Error> {
+        let (from_index, to_index) = match (
+            self.mapping(from_kind).get(from_name),
+            self.mapping(to_kind).get(to_name),
+        ) {
+            (Some(from_index), Some(to_index)) => (*from_index, *to_index),
+            _ => return Err(Error::default()),
+        };
+
+        // bfs, remembering where we came from
+        let mut parents: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+        let mut queue: VecDeque<(NodeIndex, usize)> = VecDeque::from([(from_index, 0)]);
+        let mut found = from_index == to_index;
+        while let Some((node_index, hops)) = queue.pop_front() {
+            if found || hops >= max_hops {
+                break;
+            }
+            for neighbor in self.g.neighbors(node_index) {
+                if neighbor == from_index || parents.contains_key(&neighbor) {
+                    continue;
+                }
+                parents.insert(neighbor, node_index);
+                if neighbor == to_index {
+                    found = true;
+                    break;
+                }
+                queue.push_back((neighbor, hops + 1));
+            }
+        }
+        if !found {
+            return Ok(None);
+        }
+
+        let mut chain = vec![to_index];
+        while let Some(parent) = parents.get(chain.last().unwrap()) {
+            chain.push(*parent);
+        }
+        chain.reverse();
+
+        let edges = chain
+            .windows(2)
+            .map(|pair| {
+                let edge = self.g.find_edge(pair[0], pair[1]).unwrap();
+                return self.g[edge].to_string();
+            })
+            .collect();
+        return Ok(Some(NodePath {
+            nodes: chain.iter().map(|each| self.path_node(*each)).collect(),
+            edges,
+        }));
+    }
+}
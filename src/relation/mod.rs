@@ -1,5 +1,21 @@
 pub mod graph;
+mod graph_bus;
+mod graph_centrality;
+mod graph_community;
+mod graph_conventional;
 mod graph_core;
+mod graph_coupling;
+mod graph_diff;
+mod graph_expert;
 mod graph_export;
 mod graph_ext;
+mod graph_hotness;
+mod graph_impact;
+mod graph_merge;
 mod graph_query;
+mod graph_range;
+mod graph_remove;
+mod graph_subgraph;
+mod graph_trailer;
+mod graph_traversal;
+pub mod query;
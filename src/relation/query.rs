@@ -0,0 +1,431 @@
+//! A small pattern language over the relation graph, e.g.
+//!
+//! `file[name~"src/"] -[File2Commit]-> commit[kind="fix"] -> issue | order count desc | limit 10`
+//!
+//! Each step names a node type with optional filters, `->` follows any edge and
+//! `-[Type]->` only edges of that type (trailer roles like `-["Reviewed-by"]->` work too).
+//! The result is the nodes of the last step, counted by how many paths reach them.
+
+use crate::relation::graph::{EdgeType, NodeKind, RelatedCount, RelationGraph};
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use serde_derive::Serialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    pub message: String,
+    // char offset in the expression
+    pub position: usize,
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FilterOp {
+    Eq,
+    NotEq,
+    Contains,
+}
+
+#[derive(Debug, Clone)]
+struct Filter {
+    attr: String,
+    op: FilterOp,
+    value: String,
+}
+
+#[derive(Debug, Clone)]
+struct Step {
+    kind: NodeKind,
+    filters: Vec<Filter>,
+}
+
+#[derive(Debug, Clone)]
+struct Hop {
+    edge: Option<String>,
+    step: Step,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OrderBy {
+    Count,
+    Name,
+}
+
+#[derive(Debug, Clone)]
+pub struct Query {
+    start: Step,
+    hops: Vec<Hop>,
+    order: OrderBy,
+    desc: bool,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    LBracket,
+    RBracket,
+    Comma,
+    Dash,
+    Arrow,
+    Pipe,
+    Op(FilterOp),
+}
+
+fn tokenize(expr: &str) -> Result<Vec<(Token, usize)>, QueryError> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let c = chars[pos];
+        let start = pos;
+        let token = match c {
+            _ if c.is_whitespace() => {
+                pos += 1;
+                continue;
+            }
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ',' => Token::Comma,
+            '|' => Token::Pipe,
+            '=' => Token::Op(FilterOp::Eq),
+            '~' => Token::Op(FilterOp::Contains),
+            '!' if chars.get(pos + 1) == Some(&'=') => {
+                pos += 1;
+                Token::Op(FilterOp::NotEq)
+            }
+            '-' if chars.get(pos + 1) == Some(&'>') => {
+                pos += 1;
+                Token::Arrow
+            }
+            '-' => Token::Dash,
+            '"' => {
+                let mut value = String::new();
+                pos += 1;
+                while pos < chars.len() && chars[pos] != '"' {
+                    if chars[pos] == '\\' && pos + 1 < chars.len() {
+                        pos += 1;
+                    }
+                    value.push(chars[pos]);
+                    pos += 1;
+                }
+                if pos == chars.len() {
+                    return Err(QueryError {
+                        message: String::from("unterminated string"),
+                        position: start,
+                    });
+                }
+                Token::Str(value)
+            }
+            _ if c.is_alphanumeric() || c == '_' => {
+                while pos + 1 < chars.len()
+                    && (chars[pos + 1].is_alphanumeric() || chars[pos + 1] == '_')
+                {
+                    pos += 1;
+                }
+                Token::Ident(chars[start..=pos].iter().collect())
+            }
+            _ => {
+                return Err(QueryError {
+                    message: format!("unexpected character '{}'", c),
+                    position: start,
+                })
+            }
+        };
+        tokens.push((token, start));
+        pos += 1;
+    }
+    return Ok(tokens);
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    cursor: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        return self.tokens.get(self.cursor).map(|(token, _)| token);
+    }
+
+    fn position(&self) -> usize {
+        return self
+            .tokens
+            .get(self.cursor)
+            .map(|(_, position)| *position)
+            .unwrap_or(self.end);
+    }
+
+    fn error(&self, message: String) -> QueryError {
+        return QueryError {
+            message,
+            position: self.position(),
+        };
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.cursor).map(|(token, _)| token.clone());
+        self.cursor += 1;
+        return token;
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), QueryError> {
+        if self.peek() == Some(&expected) {
+            self.cursor += 1;
+            return Ok(());
+        }
+        return Err(self.error(format!("expected {:?}", expected)));
+    }
+
+    fn word(&mut self) -> Result<String, QueryError> {
+        return match self.peek() {
+            Some(Token::Ident(word)) | Some(Token::Str(word)) => {
+                let word = word.clone();
+                self.cursor += 1;
+                Ok(word)
+            }
+            _ => Err(self.error(String::from("expected a name or string"))),
+        };
+    }
+
+    fn step(&mut self) -> Result<Step, QueryError> {
+        let position = self.position();
+        let kind = self
+            .word()?
+            .parse::<NodeKind>()
+            .map_err(|message| QueryError { message, position })?;
+
+        let mut filters = Vec::new();
+        if self.peek() == Some(&Token::LBracket) {
+            self.cursor += 1;
+            loop {
+                let position = self.position();
+                let attr = self.word()?;
+                if !attr_allowed(kind, &attr) {
+                    return Err(QueryError {
+                        message: format!("unknown attribute '{}' for {}", attr, kind),
+                        position,
+                    });
+                }
+                let op = match self.next() {
+                    Some(Token::Op(op)) => op,
+                    _ => {
+                        self.cursor -= 1;
+                        return Err(self.error(String::from("expected =, != or ~")));
+                    }
+                };
+                let value = self.word()?;
+                filters.push(Filter { attr, op, value });
+
+                match self.next() {
+                    Some(Token::Comma) => continue,
+                    Some(Token::RBracket) => break,
+                    _ => {
+                        self.cursor -= 1;
+                        return Err(self.error(String::from("expected ',' or ']'")));
+                    }
+                }
+            }
+        }
+        return Ok(Step { kind, filters });
+    }
+
+    fn parse(&mut self) -> Result<Query, QueryError> {
+        let start = self.step()?;
+
+        let mut hops = Vec::new();
+        loop {
+            let edge = match self.peek() {
+                Some(Token::Arrow) => {
+                    self.cursor += 1;
+                    None
+                }
+                Some(Token::Dash) => {
+                    self.cursor += 1;
+                    self.expect(Token::LBracket)?;
+                    let edge = self.word()?;
+                    self.expect(Token::RBracket)?;
+                    self.expect(Token::Arrow)?;
+                    Some(edge)
+                }
+                _ => break,
+            };
+            hops.push(Hop {
+                edge,
+                step: self.step()?,
+            });
+        }
+
+        let mut query = Query {
+            start,
+            hops,
+            order: OrderBy::Count,
+            desc: true,
+            limit: None,
+        };
+        while self.peek() == Some(&Token::Pipe) {
+            self.cursor += 1;
+            match self.word()?.as_str() {
+                "order" => {
+                    query.order = match self.word()?.as_str() {
+                        "count" => OrderBy::Count,
+                        "name" => OrderBy::Name,
+                        _ => {
+                            self.cursor -= 1;
+                            return Err(self.error(String::from("expected count or name")));
+                        }
+                    };
+                    // counts read best from high to low, names alphabetically
+                    query.desc = query.order == OrderBy::Count;
+                    if let Some(Token::Ident(direction)) = self.peek() {
+                        query.desc = match direction.as_str() {
+                            "asc" => false,
+                            "desc" => true,
+                            _ => return Err(self.error(String::from("expected asc or desc"))),
+                        };
+                        self.cursor += 1;
+                    }
+                }
+                "limit" => {
+                    let position = self.position();
+                    query.limit = Some(self.word()?.parse().map_err(|_| QueryError {
+                        message: String::from("expected a number"),
+                        position,
+                    })?);
+                }
+                _ => {
+                    self.cursor -= 1;
+                    return Err(self.error(String::from("expected order or limit")));
+                }
+            }
+        }
+
+        if self.peek().is_some() {
+            return Err(self.error(String::from("unexpected token")));
+        }
+        return Ok(query);
+    }
+}
+
+fn attr_allowed(kind: NodeKind, attr: &str) -> bool {
+    return match attr {
+        "name" => true,
+        "kind" | "scope" | "breaking" => kind == NodeKind::Commit,
+        _ => false,
+    };
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(expr: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(expr)?,
+            cursor: 0,
+            end: expr.chars().count(),
+        };
+        return parser.parse();
+    }
+}
+
+fn edge_matches(edge: &EdgeType, label: &str) -> bool {
+    return edge.to_string() == label || edge.roles().iter().any(|role| role == label);
+}
+
+/// evaluation
+impl RelationGraph {
+    fn attr_value(&self, node_index: NodeIndex, attr: &str) -> Option<String> {
+        let name = &self.g[node_index].name;
+        if attr == "name" {
+            return Some(name.to_string());
+        }
+        let data = self.commit_data(name)?;
+        return match attr {
+            "kind" => data.kind.clone(),
+            "scope" => data.scope.clone(),
+            "breaking" => Some(data.breaking.to_string()),
+            _ => None,
+        };
+    }
+
+    fn step_matches(&self, node_index: NodeIndex, step: &Step) -> bool {
+        if self.g[node_index]._node_type.kind() != step.kind {
+            return false;
+        }
+        return step.filters.iter().all(|filter| {
+            let value = self.attr_value(node_index, &filter.attr);
+            return match filter.op {
+                FilterOp::Eq => value.as_deref() == Some(filter.value.as_str()),
+                FilterOp::NotEq => value.as_deref() != Some(filter.value.as_str()),
+                FilterOp::Contains => value.is_some_and(|value| value.contains(&filter.value)),
+            };
+        });
+    }
+
+    pub fn run_query(&self, query: &Query) -> Vec<RelatedCount> {
+        let mut frontier: HashMap<NodeIndex, usize> = self
+            .mapping(query.start.kind)
+            .values()
+            .filter(|node_index| self.step_matches(**node_index, &query.start))
+            .map(|node_index| (*node_index, 1))
+            .collect();
+
+        for hop in &query.hops {
+            let mut next: HashMap<NodeIndex, usize> = HashMap::new();
+            for (node_index, count) in &frontier {
+                for edge in self.g.edges(*node_index) {
+                    if let Some(label) = &hop.edge {
+                        if !edge_matches(edge.weight(), label) {
+                            continue;
+                        }
+                    }
+                    let other = if edge.source() == *node_index {
+                        edge.target()
+                    } else {
+                        edge.source()
+                    };
+                    if self.step_matches(other, &hop.step) {
+                        // path counts multiply per hop, long paths can overflow
+                        let total = next.entry(other).or_insert(0);
+                        *total = total.saturating_add(*count);
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        let counter = frontier
+            .into_iter()
+            .map(|(node_index, count)| (self.g[node_index].name.to_string(), count))
+            .collect();
+        let mut result = RelationGraph::sorted_counts(counter);
+        match (&query.order, query.desc) {
+            (OrderBy::Count, true) => {}
+            (OrderBy::Count, false) => result.sort_by(|a, b| a.count.cmp(&b.count)),
+            (OrderBy::Name, false) => result.sort_by(|a, b| a.name.cmp(&b.name)),
+            (OrderBy::Name, true) => result.sort_by(|a, b| b.name.cmp(&a.name)),
+        }
+        if let Some(limit) = query.limit {
+            result.truncate(limit);
+        }
+        return result;
+    }
+
+    /// Parse and run a query expression, see the module docs for the syntax.
+    pub fn query(&self, expr: &str) -> Result<Vec<RelatedCount>, QueryError> {
+        let query: Query = expr.parse()?;
+        return Ok(self.run_query(&query));
+    }
+}
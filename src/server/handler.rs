@@ -1,10 +1,23 @@
 use crate::collector::config::Config;
 use crate::relation::graph::GraphSize;
 use crate::server::handler_ext::{
-    author_related_commits_handler, authors, commit_related_authors_handler,
+    author_related_commits_handler, author_related_files_handler, author_related_issues_handler,
+    authors, bus_factor_report_handler, centrality_handler, commit_info_handler,
+    commit_related_authors_handler, commit_related_persons_handler, communities_handler,
+    coupling_report_handler, dir_bus_factor_handler, dir_experts_handler, file_bus_factor_handler,
+    file_commit_kinds_handler, file_coupled_files_handler, file_experts_handler, file_hot_handler,
+    file_owners_handler, file_range_related_authors_handler, file_range_related_commits_handler,
+    file_range_related_issues_handler, file_related_authors_handler,
+    file_related_commits_by_kind_handler, file_related_reviewers_handler,
+    file_related_scopes_handler, file_related_symbols_handler, hot_files_handler,
+    issue_experts_handler, issue_related_authors_handler, person_related_commits_handler, persons,
+    query_handler, reviewer_load_handler, scope_related_commits_handler,
+    scope_related_files_handler, scopes, shortest_path_handler, subgraph_dot_handler,
+    subgraph_handler, symbol_related_commits_handler, symbol_related_issues_handler, symbols,
+    traverse_handler, worktree_impact_handler,
 };
 use axum::extract::Query;
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::Router;
 use serde_derive::{Deserialize, Serialize};
 use tracing::error;
@@ -22,6 +35,8 @@ pub fn create_router() -> Router {
             Router::new()
                 .route("/-/files", get(issue_related_files_handler))
                 .route("/-/commits", get(issue_related_commits_handler))
+                .route("/-/experts", get(issue_experts_handler))
+                .route("/-/authors", get(issue_related_authors_handler))
                 .route("/list", get(issues)),
         )
         .nest(
@@ -37,12 +52,76 @@ pub fn create_router() -> Router {
             "/author",
             Router::new()
                 .route("/-/commits", get(author_related_commits_handler))
+                .route("/-/files", get(author_related_files_handler))
+                .route("/-/issues", get(author_related_issues_handler))
                 .route("/list", get(authors)),
         )
         .nest(
             "/commit",
-            Router::new().route("/-/authors", get(commit_related_authors_handler)),
-        );
+            Router::new()
+                .route("/-/authors", get(commit_related_authors_handler))
+                .route("/-/info", get(commit_info_handler))
+                .route("/-/persons", get(commit_related_persons_handler)),
+        )
+        .nest(
+            "/symbol",
+            Router::new()
+                .route("/-/commits", get(symbol_related_commits_handler))
+                .route("/-/issues", get(symbol_related_issues_handler))
+                .route("/list", get(symbols)),
+        )
+        .nest(
+            "/file",
+            Router::new()
+                .route("/-/symbols", get(file_related_symbols_handler))
+                .route("/-/authors", get(file_related_authors_handler))
+                .route("/-/range/commits", get(file_range_related_commits_handler))
+                .route("/-/range/issues", get(file_range_related_issues_handler))
+                .route("/-/range/authors", get(file_range_related_authors_handler))
+                .route("/-/owners", get(file_owners_handler))
+                .route("/-/kinds", get(file_commit_kinds_handler))
+                .route("/-/kind/commits", get(file_related_commits_by_kind_handler))
+                .route("/-/scopes", get(file_related_scopes_handler))
+                .route("/-/reviewers", get(file_related_reviewers_handler))
+                .route("/-/coupled", get(file_coupled_files_handler))
+                .route("/-/hot", get(file_hot_handler))
+                .route("/-/bus", get(file_bus_factor_handler))
+                .route("/-/experts", get(file_experts_handler))
+                .route("/hot", get(hot_files_handler)),
+        )
+        .nest(
+            "/scope",
+            Router::new()
+                .route("/-/files", get(scope_related_files_handler))
+                .route("/-/commits", get(scope_related_commits_handler))
+                .route("/list", get(scopes)),
+        )
+        .nest(
+            "/person",
+            Router::new()
+                .route("/-/commits", get(person_related_commits_handler))
+                .route("/load", get(reviewer_load_handler))
+                .route("/list", get(persons)),
+        )
+        .nest(
+            "/worktree",
+            Router::new().route("/impact", get(worktree_impact_handler)),
+        )
+        .nest(
+            "/dir",
+            Router::new()
+                .route("/-/bus", get(dir_bus_factor_handler))
+                .route("/-/experts", get(dir_experts_handler)),
+        )
+        .route("/coupling", get(coupling_report_handler))
+        .route("/bus", get(bus_factor_report_handler))
+        .route("/communities", get(communities_handler))
+        .route("/centrality", get(centrality_handler))
+        .route("/subgraph", get(subgraph_handler))
+        .route("/subgraph/dot", get(subgraph_dot_handler))
+        .route("/traverse", get(traverse_handler))
+        .route("/path", get(shortest_path_handler))
+        .route("/query", post(query_handler));
 }
 
 async fn size_handler() -> axum::Json<GraphSize> {
@@ -55,6 +134,7 @@ async fn root_handler() -> axum::Json<Desc> {
     axum::Json(Desc {
         version: crate::server::app::VERSION.to_string(),
         graph_conf: conf.graph.conf.clone(),
+        complete: conf.graph.is_complete(),
     })
 }
 
@@ -136,8 +216,8 @@ async fn commit_related_issues_handler(
 }
 
 #[derive(Debug, Deserialize)]
-struct FileParams {
-    file: String,
+pub(crate) struct FileParams {
+    pub(crate) file: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -146,12 +226,13 @@ pub(crate) struct CommitParams {
 }
 
 #[derive(Debug, Deserialize)]
-struct IssueParams {
-    issue: String,
+pub(crate) struct IssueParams {
+    pub(crate) issue: String,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 struct Desc {
     version: String,
     graph_conf: Config,
+    complete: bool,
 }
@@ -1,6 +1,15 @@
-use crate::server::handler::CommitParams;
+use crate::collector::worktree::worktree_changed_files;
+use crate::relation::graph::{
+    BusFactor, BusFactorOptions, BusFactorReport, Centrality, CentralityMetric, CentralityOptions,
+    ChangeImpact, CommitInfo, Community, CommunityOptions, Coupling, Expert, ExpertModel,
+    ExpertTarget, FileOwners, GraphExport, Hotness, NodeKind, NodePath, PersonRole, RelatedCount,
+    RelationGraph, SubgraphOptions,
+};
+use crate::relation::query::QueryError;
+use crate::server::handler::{CommitParams, FileParams, IssueParams};
 use axum::extract::Query;
-use serde_derive::Deserialize;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
 use tracing::error;
 
 #[derive(Debug, Deserialize)]
@@ -8,6 +17,106 @@ pub(crate) struct AuthorParams {
     author: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub(crate) struct FileRangeParams {
+    file: String,
+    start: u32,
+    end: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct FileKindParams {
+    file: String,
+    kind: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CouplingParams {
+    file: Option<String>,
+    min_support: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct TraverseParams {
+    #[serde(rename = "type")]
+    kind: NodeKind,
+    name: String,
+    /// comma separated node types, e.g. `issue,file`
+    path: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PathParams {
+    from_type: NodeKind,
+    from: String,
+    to_type: NodeKind,
+    to: String,
+    max_hops: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct QueryBody {
+    query: String,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct QueryResponse {
+    result: Vec<RelatedCount>,
+    error: Option<QueryError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct TopParams {
+    top: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct DirParams {
+    dir: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CentralityParams {
+    metric: Option<CentralityMetric>,
+    kind: Option<NodeKind>,
+    /// comma separated node types, e.g. `file,issue`
+    within: Option<String>,
+    top: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct SubgraphParams {
+    #[serde(rename = "type")]
+    kind: NodeKind,
+    name: String,
+    hops: Option<usize>,
+    /// comma separated node types to keep, e.g. `file,issue`
+    kinds: Option<String>,
+    max_nodes: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ScopeParams {
+    scope: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PersonParams {
+    person: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ReviewerParams {
+    file: Option<String>,
+    dir: Option<String>,
+    role: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct SymbolParams {
+    symbol: String,
+}
+
 pub(crate) async fn author_related_commits_handler(
     Query(params): Query<AuthorParams>,
 ) -> axum::Json<Vec<String>> {
@@ -34,7 +143,572 @@ pub(crate) async fn commit_related_authors_handler(
     };
 }
 
+pub(crate) async fn author_related_files_handler(
+    Query(params): Query<AuthorParams>,
+) -> axum::Json<Vec<RelatedCount>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.author_related_files(&params.author) {
+        Ok(related) => axum::Json(related),
+        Err(error) => {
+            error!("author_related_files error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn author_related_issues_handler(
+    Query(params): Query<AuthorParams>,
+) -> axum::Json<Vec<RelatedCount>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.author_related_issues(&params.author) {
+        Ok(related) => axum::Json(related),
+        Err(error) => {
+            error!("author_related_issues error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn file_related_authors_handler(
+    Query(params): Query<FileParams>,
+) -> axum::Json<Vec<RelatedCount>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.file_related_authors(&params.file) {
+        Ok(related) => axum::Json(related),
+        Err(error) => {
+            error!("file_related_authors error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn issue_related_authors_handler(
+    Query(params): Query<IssueParams>,
+) -> axum::Json<Vec<RelatedCount>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.issue_related_authors(&params.issue) {
+        Ok(related) => axum::Json(related),
+        Err(error) => {
+            error!("issue_related_authors error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
 pub(crate) async fn authors() -> axum::Json<Vec<String>> {
     let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
     return axum::Json(conf.graph.authors());
 }
+
+pub(crate) async fn file_related_symbols_handler(
+    Query(params): Query<FileParams>,
+) -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.file_related_symbols(&params.file) {
+        Ok(symbols) => axum::Json(symbols),
+        Err(error) => {
+            error!("file_related_symbols error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn symbol_related_commits_handler(
+    Query(params): Query<SymbolParams>,
+) -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.symbol_related_commits(&params.symbol) {
+        Ok(commits) => axum::Json(commits),
+        Err(error) => {
+            error!("symbol_related_commits error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn symbol_related_issues_handler(
+    Query(params): Query<SymbolParams>,
+) -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.symbol_related_issues(&params.symbol) {
+        Ok(issues) => axum::Json(issues),
+        Err(error) => {
+            error!("symbol_related_issues error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn symbols() -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return axum::Json(conf.graph.symbols());
+}
+
+pub(crate) async fn file_range_related_commits_handler(
+    Query(params): Query<FileRangeParams>,
+) -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf
+        .graph
+        .file_range_related_commits(&params.file, params.start, params.end)
+    {
+        Ok(commits) => axum::Json(commits),
+        Err(error) => {
+            error!("file_range_related_commits error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn file_range_related_issues_handler(
+    Query(params): Query<FileRangeParams>,
+) -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf
+        .graph
+        .file_range_related_issues(&params.file, params.start, params.end)
+    {
+        Ok(issues) => axum::Json(issues),
+        Err(error) => {
+            error!("file_range_related_issues error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn file_range_related_authors_handler(
+    Query(params): Query<FileRangeParams>,
+) -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf
+        .graph
+        .file_range_related_authors(&params.file, params.start, params.end)
+    {
+        Ok(authors) => axum::Json(authors),
+        Err(error) => {
+            error!("file_range_related_authors error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn file_owners_handler(
+    Query(params): Query<FileParams>,
+) -> axum::Json<FileOwners> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.file_owners(&params.file) {
+        Ok(owners) => axum::Json(owners),
+        Err(error) => {
+            error!("file_owners error: {}", error);
+            axum::Json(FileOwners::default())
+        }
+    };
+}
+
+pub(crate) async fn commit_info_handler(
+    Query(params): Query<CommitParams>,
+) -> axum::Json<CommitInfo> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.commit_info(&params.commit) {
+        Ok(info) => axum::Json(info),
+        Err(error) => {
+            error!("commit_info error: {}", error);
+            axum::Json(CommitInfo::default())
+        }
+    };
+}
+
+pub(crate) async fn file_commit_kinds_handler(
+    Query(params): Query<FileParams>,
+) -> axum::Json<HashMap<String, usize>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.file_commit_kinds(&params.file) {
+        Ok(kinds) => axum::Json(kinds),
+        Err(error) => {
+            error!("file_commit_kinds error: {}", error);
+            axum::Json(HashMap::new())
+        }
+    };
+}
+
+pub(crate) async fn file_related_commits_by_kind_handler(
+    Query(params): Query<FileKindParams>,
+) -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf
+        .graph
+        .file_related_commits_by_kind(&params.file, &params.kind)
+    {
+        Ok(commits) => axum::Json(commits),
+        Err(error) => {
+            error!("file_related_commits_by_kind error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn file_related_scopes_handler(
+    Query(params): Query<FileParams>,
+) -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.file_related_scopes(&params.file) {
+        Ok(scopes) => axum::Json(scopes),
+        Err(error) => {
+            error!("file_related_scopes error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn scope_related_files_handler(
+    Query(params): Query<ScopeParams>,
+) -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.scope_related_files(&params.scope) {
+        Ok(files) => axum::Json(files),
+        Err(error) => {
+            error!("scope_related_files error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn scope_related_commits_handler(
+    Query(params): Query<ScopeParams>,
+) -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.scope_related_commits(&params.scope) {
+        Ok(commits) => axum::Json(commits),
+        Err(error) => {
+            error!("scope_related_commits error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn scopes() -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return axum::Json(conf.graph.scopes());
+}
+
+pub(crate) async fn person_related_commits_handler(
+    Query(params): Query<PersonParams>,
+) -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.person_related_commits(&params.person) {
+        Ok(commits) => axum::Json(commits),
+        Err(error) => {
+            error!("person_related_commits error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn commit_related_persons_handler(
+    Query(params): Query<CommitParams>,
+) -> axum::Json<Vec<PersonRole>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.commit_related_persons(&params.commit) {
+        Ok(persons) => axum::Json(persons),
+        Err(error) => {
+            error!("commit_related_persons error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn file_related_reviewers_handler(
+    Query(params): Query<ReviewerParams>,
+) -> axum::Json<HashMap<String, usize>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    let role = params.role.as_deref();
+    if let Some(dir) = &params.dir {
+        return axum::Json(conf.graph.dir_related_reviewers(dir, role));
+    }
+    return match conf
+        .graph
+        .file_related_reviewers(&params.file.unwrap_or_default(), role)
+    {
+        Ok(reviewers) => axum::Json(reviewers),
+        Err(error) => {
+            error!("file_related_reviewers error: {}", error);
+            axum::Json(HashMap::new())
+        }
+    };
+}
+
+pub(crate) async fn reviewer_load_handler(
+    Query(params): Query<ReviewerParams>,
+) -> axum::Json<HashMap<String, usize>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return axum::Json(conf.graph.reviewer_load(params.role.as_deref()));
+}
+
+pub(crate) async fn persons() -> axum::Json<Vec<String>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return axum::Json(conf.graph.persons());
+}
+
+pub(crate) async fn worktree_impact_handler() -> axum::Json<ChangeImpact> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    let graph_conf = &conf.graph.conf;
+    return match worktree_changed_files(&graph_conf.repo_path, &graph_conf.path_specs) {
+        Ok(files) => axum::Json(conf.graph.change_impact(&files)),
+        Err(error) => {
+            error!("worktree_impact error: {}", error);
+            axum::Json(ChangeImpact::default())
+        }
+    };
+}
+
+pub(crate) async fn file_coupled_files_handler(
+    Query(params): Query<CouplingParams>,
+) -> axum::Json<Vec<Coupling>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.file_coupled_files(
+        &params.file.unwrap_or_default(),
+        params.min_support.unwrap_or(1),
+    ) {
+        Ok(couplings) => axum::Json(couplings),
+        Err(error) => {
+            error!("file_coupled_files error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn coupling_report_handler(
+    Query(params): Query<CouplingParams>,
+) -> axum::Json<Vec<Coupling>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return axum::Json(conf.graph.coupling_report(params.min_support.unwrap_or(2)));
+}
+
+pub(crate) async fn traverse_handler(
+    Query(params): Query<TraverseParams>,
+) -> axum::Json<Vec<RelatedCount>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    let path: Result<Vec<NodeKind>, String> = params
+        .path
+        .split(",")
+        .filter(|each| !each.is_empty())
+        .map(|each| each.trim().parse())
+        .collect();
+    let path = match path {
+        Ok(path) => path,
+        Err(error) => {
+            error!("traverse error: {}", error);
+            return axum::Json(Vec::new());
+        }
+    };
+    return match conf.graph.traverse_scored(params.kind, &params.name, &path) {
+        Ok(related) => axum::Json(related),
+        Err(error) => {
+            error!("traverse error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn shortest_path_handler(
+    Query(params): Query<PathParams>,
+) -> axum::Json<Option<NodePath>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.shortest_path(
+        params.from_type,
+        &params.from,
+        params.to_type,
+        &params.to,
+        params.max_hops.unwrap_or(4),
+    ) {
+        Ok(path) => axum::Json(path),
+        Err(error) => {
+            error!("shortest_path error: {}", error);
+            axum::Json(None)
+        }
+    };
+}
+
+pub(crate) async fn query_handler(
+    axum::Json(body): axum::Json<QueryBody>,
+) -> axum::Json<QueryResponse> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.query(&body.query) {
+        Ok(result) => axum::Json(QueryResponse {
+            result,
+            error: None,
+        }),
+        // analysts need to see what is wrong with their query
+        Err(error) => {
+            error!("query error: {}", error);
+            axum::Json(QueryResponse {
+                result: Vec::new(),
+                error: Some(error),
+            })
+        }
+    };
+}
+
+pub(crate) async fn hot_files_handler(Query(params): Query<TopParams>) -> axum::Json<Vec<Hotness>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return axum::Json(conf.graph.hot_files(params.top.unwrap_or(10)));
+}
+
+pub(crate) async fn file_hot_handler(
+    Query(params): Query<FileParams>,
+) -> axum::Json<Option<Hotness>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return axum::Json(conf.graph.file_hot(&params.file));
+}
+
+pub(crate) async fn file_bus_factor_handler(
+    Query(params): Query<FileParams>,
+    Query(options): Query<BusFactorOptions>,
+) -> axum::Json<Option<BusFactor>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.file_bus_factor(&params.file, &options) {
+        Ok(bus_factor) => axum::Json(Some(bus_factor)),
+        Err(error) => {
+            error!("file_bus_factor error: {}", error);
+            axum::Json(None)
+        }
+    };
+}
+
+pub(crate) async fn dir_bus_factor_handler(
+    Query(params): Query<DirParams>,
+    Query(options): Query<BusFactorOptions>,
+) -> axum::Json<Option<BusFactor>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.dir_bus_factor(&params.dir, &options) {
+        Ok(bus_factor) => axum::Json(Some(bus_factor)),
+        Err(error) => {
+            error!("dir_bus_factor error: {}", error);
+            axum::Json(None)
+        }
+    };
+}
+
+pub(crate) async fn bus_factor_report_handler(
+    Query(options): Query<BusFactorOptions>,
+) -> axum::Json<BusFactorReport> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return axum::Json(conf.graph.bus_factor_report(&options));
+}
+
+fn experts(target: ExpertTarget, model: ExpertModel) -> axum::Json<Vec<Expert>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return match conf.graph.experts_for(&target, &model) {
+        Ok(experts) => axum::Json(experts),
+        Err(error) => {
+            error!("experts_for error: {}", error);
+            axum::Json(Vec::new())
+        }
+    };
+}
+
+pub(crate) async fn file_experts_handler(
+    Query(params): Query<FileParams>,
+    Query(model): Query<ExpertModel>,
+) -> axum::Json<Vec<Expert>> {
+    return experts(ExpertTarget::File(params.file), model);
+}
+
+pub(crate) async fn dir_experts_handler(
+    Query(params): Query<DirParams>,
+    Query(model): Query<ExpertModel>,
+) -> axum::Json<Vec<Expert>> {
+    return experts(ExpertTarget::Dir(params.dir), model);
+}
+
+pub(crate) async fn issue_experts_handler(
+    Query(params): Query<IssueParams>,
+    Query(model): Query<ExpertModel>,
+) -> axum::Json<Vec<Expert>> {
+    return experts(ExpertTarget::Issue(params.issue), model);
+}
+
+pub(crate) async fn communities_handler(
+    Query(options): Query<CommunityOptions>,
+) -> axum::Json<Vec<Community>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    return axum::Json(conf.graph.communities(&options));
+}
+
+pub(crate) async fn centrality_handler(
+    Query(params): Query<CentralityParams>,
+) -> axum::Json<Vec<Centrality>> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    let mut options = CentralityOptions::default();
+    if let Some(metric) = params.metric {
+        options.metric = metric;
+    }
+    if let Some(kind) = params.kind {
+        options.kind = kind;
+    }
+    if let Some(within) = params.within {
+        let within: Result<Vec<NodeKind>, String> = within
+            .split(",")
+            .filter(|each| !each.is_empty())
+            .map(|each| each.trim().parse())
+            .collect();
+        options.within = match within {
+            Ok(within) => within,
+            Err(error) => {
+                error!("centrality error: {}", error);
+                return axum::Json(Vec::new());
+            }
+        };
+    }
+    let mut ranking = conf.graph.centrality(&options);
+    ranking.truncate(params.top.unwrap_or(10));
+    return axum::Json(ranking);
+}
+
+fn subgraph(params: SubgraphParams) -> Option<RelationGraph> {
+    let conf = crate::server::app::SERVER_CONFIG.read().unwrap();
+    let mut options = SubgraphOptions::default();
+    if let Some(hops) = params.hops {
+        options.hops = hops;
+    }
+    if let Some(max_nodes) = params.max_nodes {
+        options.max_nodes = max_nodes;
+    }
+    if let Some(kinds) = params.kinds {
+        let kinds: Result<Vec<NodeKind>, String> = kinds
+            .split(",")
+            .filter(|each| !each.is_empty())
+            .map(|each| each.trim().parse())
+            .collect();
+        options.kinds = match kinds {
+            Ok(kinds) => kinds,
+            Err(error) => {
+                error!("subgraph error: {}", error);
+                return None;
+            }
+        };
+    }
+    return match conf.graph.subgraph(params.kind, &params.name, &options) {
+        Ok(graph) => Some(graph),
+        Err(error) => {
+            error!("subgraph error: {}", error);
+            None
+        }
+    };
+}
+
+pub(crate) async fn subgraph_handler(
+    Query(params): Query<SubgraphParams>,
+) -> axum::Json<GraphExport> {
+    return match subgraph(params) {
+        Some(graph) => axum::Json(graph.export_json()),
+        None => axum::Json(GraphExport::default()),
+    };
+}
+
+pub(crate) async fn subgraph_dot_handler(Query(params): Query<SubgraphParams>) -> String {
+    return match subgraph(params) {
+        Some(graph) => graph.to_dot(),
+        None => String::new(),
+    };
+}
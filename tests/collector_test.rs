@@ -0,0 +1,636 @@
+mod common;
+
+use common::TestRepo;
+use cupido::collector::config::{get_collector, Collect, Config};
+use cupido::collector::diff::{patch_changed_files, range_changed_files, rev_added_removed_files};
+use cupido::collector::observer::{CommitEvent, WalkObserver};
+use cupido::collector::worktree::worktree_changed_files;
+use cupido::relation::graph::{GraphDiffOptions, HotnessModel, RelationGraph};
+use std::time::Duration;
+
+#[derive(Default)]
+struct RecordObserver {
+    total: Option<u64>,
+    commits: Vec<String>,
+    issues: Vec<String>,
+    skipped: usize,
+    finished: bool,
+}
+
+impl WalkObserver for RecordObserver {
+    fn needs_total(&self) -> bool {
+        true
+    }
+
+    fn on_start(&mut self, total: Option<u64>) {
+        self.total = total;
+    }
+
+    fn on_commit(&mut self, event: &CommitEvent) {
+        assert!(!event.files.is_empty());
+        self.commits.push(event.commit_id.to_string());
+        self.issues.extend(event.issues.iter().cloned());
+    }
+
+    fn on_skip(&mut self, _commit_id: &str) {
+        self.skipped += 1;
+    }
+
+    fn on_finish(&mut self) {
+        self.finished = true;
+    }
+}
+
+#[test]
+fn walk_observer() {
+    let mut repo = TestRepo::new("observer");
+    repo.commit(&[("a.txt", "a")], "init", "alice");
+    let second = repo.commit(&[("b.txt", "b")], "add b #1", "bob");
+    let third = repo.commit(&[("a.txt", "aa")], "update a #2", "alice");
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    let mut observer = RecordObserver::default();
+    let graph = get_collector().walk_with_observer(conf, &mut observer);
+
+    assert_eq!(observer.total, Some(3));
+    assert_eq!(observer.commits, vec![third, second]);
+    // root commit has no parent to diff with
+    assert_eq!(observer.skipped, 1);
+    assert!(observer.finished);
+    assert_eq!(graph.commit_size(), 2);
+
+    // counting stops at the depth limit, the total is unknown then
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.depth = 2;
+    let mut observer = RecordObserver::default();
+    get_collector().walk_with_observer(conf, &mut observer);
+    assert_eq!(observer.total, None);
+}
+
+#[test]
+fn walk_cancel() {
+    let mut repo = TestRepo::new("cancel");
+    repo.commit(&[("a.txt", "a")], "init", "alice");
+    let head = repo.commit(&[("a.txt", "aa")], "update a #1", "alice");
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    let graph = get_collector().walk(conf.clone());
+    assert!(graph.is_complete());
+    assert_eq!(graph.last_commit(), Some(&head));
+
+    conf.cancel_token.cancel();
+    let graph = get_collector().walk(conf.clone());
+    assert!(!graph.is_complete());
+    assert_eq!(graph.last_commit(), None);
+    assert_eq!(graph.commit_size(), 0);
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.time_budget = Some(Duration::ZERO);
+    let graph = get_collector().walk(conf);
+    assert!(!graph.is_complete());
+}
+
+#[test]
+fn walk_symbols() {
+    let mut repo = TestRepo::new("symbols");
+    repo.commit(
+        &[(
+            "src/foo.rs",
+            "fn parse_config() {\n    let a = 1;\n}\n\nfn other() {\n}\n",
+        )],
+        "init",
+        "alice",
+    );
+    repo.commit(
+        &[(
+            "src/foo.rs",
+            "fn parse_config() {\n    let a = 2;\n}\n\nfn other() {\n}\n",
+        )],
+        "fix config #7",
+        "alice",
+    );
+    repo.commit(
+        &[(
+            "src/foo.rs",
+            "fn added() {\n}\n\nfn parse_config() {\n    let a = 2;\n}\n\nfn other() {\n}\n",
+        )],
+        "add func #8",
+        "bob",
+    );
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.symbols = true;
+    let graph = get_collector().walk(conf);
+
+    let parse_config = String::from("src/foo.rs::parse_config");
+    assert_eq!(
+        graph.symbol_related_issues(&parse_config).unwrap(),
+        vec!["#7"]
+    );
+    assert_eq!(
+        graph
+            .symbol_related_issues(&String::from("src/foo.rs::added"))
+            .unwrap(),
+        vec!["#8"]
+    );
+    assert!(graph
+        .get_symbol_node(&String::from("src/foo.rs::other"))
+        .is_none());
+    assert_eq!(
+        graph
+            .file_related_symbols(&String::from("src/foo.rs"))
+            .unwrap()
+            .len(),
+        2
+    );
+
+    // disabled by default
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    assert!(get_collector().walk(conf).symbols().is_empty());
+}
+
+#[test]
+fn walk_line_ranges() {
+    let mut repo = TestRepo::new("ranges");
+    repo.commit(&[("a.rs", "1\n2\n3\n4\n5\n")], "init", "alice");
+    repo.commit(&[("a.rs", "1\n2\n3\n4\n5\n6\n7\n")], "append #1", "alice");
+    repo.commit(
+        &[("a.rs", "x\ny\n1\n2\n3\n4\n5\n6\n7\n")],
+        "prepend #2",
+        "bob",
+    );
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.line_ranges = true;
+    let graph = get_collector().walk(conf);
+
+    let file = String::from("a.rs");
+    assert_eq!(
+        graph.file_range_related_issues(&file, 8, 9).unwrap(),
+        vec!["#1"]
+    );
+    assert_eq!(
+        graph.file_range_related_issues(&file, 1, 1).unwrap(),
+        vec!["#2"]
+    );
+    assert!(graph
+        .file_range_related_issues(&file, 3, 7)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        graph.file_range_related_commits(&file, 1, 9).unwrap().len(),
+        2
+    );
+    assert_eq!(
+        graph.file_range_related_authors(&file, 9, 20).unwrap(),
+        vec!["alice <alice@example.com>"]
+    );
+    assert!(graph
+        .file_range_related_issues(&String::from("b.rs"), 1, 1)
+        .is_err());
+}
+
+#[test]
+fn walk_line_ranges_merge() {
+    let mut repo = TestRepo::new("ranges-merge");
+    let base = repo.commit(&[("a.rs", "1\n2\n3\n")], "init", "alice");
+    let side = repo.branch_commit(&base, &[("a.rs", "1\n2\n3\n4\n")], "append #1", "bob");
+    repo.write("a.rs", "1\n2\n3\n");
+    repo.commit(&[("a.rs", "0\n1\n2\n3\n")], "prepend #2", "alice");
+    repo.merge(&side, &[("a.rs", "0\n1\n2\n3\n4\n")], "merge #3", "alice");
+
+    // first parent: the merge brings the side branch lines
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.line_ranges = true;
+    let graph = get_collector().walk(conf);
+    let file = String::from("a.rs");
+    assert_eq!(
+        graph.file_range_related_issues(&file, 1, 1).unwrap(),
+        vec!["#2"]
+    );
+    assert_eq!(
+        graph.file_range_related_issues(&file, 5, 5).unwrap(),
+        vec!["#3"]
+    );
+
+    // branches interleave, no ranges rather than wrong ones
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.line_ranges = true;
+    conf.multi_parents = true;
+    let graph = get_collector().walk(conf);
+    assert_eq!(graph.commit_size(), 3);
+    assert!(graph
+        .file_range_related_commits(&file, 1, 5)
+        .unwrap_or_default()
+        .is_empty());
+}
+
+#[test]
+fn walk_blame() {
+    let mut repo = TestRepo::new("blame");
+    repo.commit(&[("a.rs", "1\n")], "init", "alice");
+    repo.commit(&[("a.rs", "1\n2\n3\n4\n")], "more #1", "alice");
+    repo.commit(&[("a.rs", "1\n2\n3\n4\n5\n6\n7\n8\n")], "more #2", "bob");
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.blame = true;
+    let graph = get_collector().walk(conf);
+
+    let owners = graph.file_owners(&String::from("a.rs")).unwrap();
+    assert_eq!(owners.total_lines, 8);
+    assert_eq!(owners.authors[0].name, "alice <alice@example.com>");
+    assert_eq!(owners.authors[0].lines, 4);
+    assert_eq!(owners.authors[1].share, 0.5);
+    assert_eq!(owners.issues.len(), 2);
+    assert_eq!(owners.issues[0].lines, 4);
+    assert!(graph.file_owners(&String::from("b.rs")).is_err());
+}
+
+#[test]
+fn walk_blame_repeated_issue() {
+    let mut repo = TestRepo::new("blame-repeat");
+    repo.commit(&[("a.rs", "0\n")], "init", "alice");
+    repo.commit(&[("a.rs", "1\n2\n3\n4\n")], "fix #1 (see #1)", "alice");
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.blame = true;
+    let graph = get_collector().walk(conf);
+
+    let owners = graph.file_owners(&String::from("a.rs")).unwrap();
+    assert_eq!(owners.issues.len(), 1);
+    assert_eq!(owners.issues[0].lines, 4);
+    assert_eq!(owners.issues[0].share, 1.0);
+}
+
+#[test]
+fn merge_blame_conflicts() {
+    let mut repo = TestRepo::new("merge-blame");
+    repo.commit(&[("a.rs", "0\n")], "init", "alice");
+    repo.commit(&[("a.rs", "1\n2\n")], "two lines", "alice");
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.blame = true;
+    let mut graph = get_collector().walk(conf.clone());
+
+    // same length, other owner
+    repo.commit(&[("a.rs", "1\n3\n")], "change a line", "bob");
+    let other = get_collector().walk(conf);
+
+    let report = graph.merge(&other);
+    let fields: Vec<&str> = report
+        .conflicts
+        .iter()
+        .filter(|each| each.kind.is_some())
+        .map(|each| each.field.as_str())
+        .collect();
+    assert_eq!(fields, vec!["author_lines"]);
+    let conflict = &report
+        .conflicts
+        .iter()
+        .find(|each| each.kind.is_some())
+        .unwrap();
+    assert_eq!(conflict.ours, "alice <alice@example.com>: 2");
+    assert!(conflict.theirs.contains("bob <bob@example.com>: 1"));
+}
+
+#[test]
+fn walk_conventional() {
+    let mut repo = TestRepo::new("conventional");
+    repo.commit(&[("a.rs", "1")], "init", "alice");
+    let feat = repo.commit(&[("a.rs", "2")], "feat(server)!: new api #1", "alice");
+    let fix = repo.commit(
+        &[("a.rs", "3"), ("b.rs", "3")],
+        "fix(server): crash\n\nfix #2",
+        "bob",
+    );
+    repo.commit(&[("a.rs", "4")], "Fix: another one", "bob");
+    repo.commit(&[("a.rs", "5")], "random change", "bob");
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.scope_nodes = true;
+    let graph = get_collector().walk(conf);
+
+    let info = graph.commit_info(&feat).unwrap();
+    assert_eq!(info.kind.as_deref(), Some("feat"));
+    assert_eq!(info.scope.as_deref(), Some("server"));
+    assert!(info.breaking);
+    assert!(!graph.commit_info(&fix).unwrap().breaking);
+
+    let file = String::from("a.rs");
+    assert_eq!(
+        graph
+            .file_related_commits_by_kind(&file, "fix")
+            .unwrap()
+            .len(),
+        2
+    );
+    let kinds = graph.file_commit_kinds(&file).unwrap();
+    assert_eq!(kinds.get("feat"), Some(&1));
+    assert_eq!(kinds.len(), 2);
+    assert_eq!(graph.file_fix_feature_ratio(&file).unwrap(), Some(2.0));
+    assert_eq!(
+        graph.file_fix_feature_ratio(&String::from("b.rs")).unwrap(),
+        None
+    );
+
+    assert_eq!(graph.scopes(), vec!["server"]);
+    assert_eq!(
+        graph
+            .scope_related_files(&String::from("server"))
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn walk_trailers() {
+    let mut repo = TestRepo::new("trailers");
+    repo.commit(&[("src/a.rs", "1")], "init", "alice");
+    let first = repo.commit(
+        &[("src/a.rs", "2")],
+        "change a\n\nReviewed-by: Carol <carol@example.com>\nSigned-off-by: alice <alice@example.com>",
+        "alice",
+    );
+    repo.commit(
+        &[("src/b.rs", "2")],
+        "change b\n\nreviewed-by: Carol <carol@example.com>\nAcked-by: Dave <dave@example.com>",
+        "bob",
+    );
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.trailers = vec![String::from("Reviewed-by"), String::from("Signed-off-by")];
+    let graph = get_collector().walk(conf);
+
+    let carol = String::from("Carol <carol@example.com>");
+    assert_eq!(graph.persons().len(), 2);
+    assert_eq!(graph.person_related_commits(&carol).unwrap().len(), 2);
+
+    // alice signing off her own commit is still one author
+    let alice = String::from("alice <alice@example.com>");
+    assert_eq!(
+        graph.commit_related_authors(&first).unwrap(),
+        vec![alice.clone()]
+    );
+    assert_eq!(
+        graph.person_related_commits(&alice).unwrap(),
+        vec![first.clone()]
+    );
+    assert_eq!(
+        graph.author_related_commits(&alice).unwrap(),
+        vec![first.clone()]
+    );
+
+    let persons = graph.commit_related_persons(&first).unwrap();
+    assert_eq!(persons.len(), 2);
+    assert!(persons
+        .iter()
+        .any(|each| each.person == carol && each.role == "Reviewed-by"));
+
+    let reviewers = graph
+        .file_related_reviewers(&String::from("src/a.rs"), Some("reviewed-by"))
+        .unwrap();
+    assert_eq!(reviewers.len(), 1);
+    assert_eq!(reviewers.get(&carol), Some(&1));
+    let dir_reviewers = graph.dir_related_reviewers(&String::from("src/"), Some("Reviewed-by"));
+    assert_eq!(dir_reviewers.get(&carol), Some(&2));
+    assert_eq!(graph.reviewer_load(None).values().sum::<usize>(), 3);
+}
+
+#[test]
+fn walk_trailer_roles() {
+    let mut repo = TestRepo::new("trailer-roles");
+    repo.commit(&[("src/a.rs", "1")], "init", "alice");
+    let commit = repo.commit(
+        &[("src/a.rs", "2")],
+        "change a\n\nReviewed-by: Carol <carol@example.com>\nSigned-off-by: Carol <carol@example.com>",
+        "bob",
+    );
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.trailers = vec![String::from("Reviewed-by"), String::from("Signed-off-by")];
+    let mut graph = get_collector().walk(conf);
+
+    let mut roles: Vec<String> = graph
+        .commit_related_persons(&commit)
+        .unwrap()
+        .into_iter()
+        .map(|each| each.role)
+        .collect();
+    roles.sort();
+    assert_eq!(roles, vec!["Reviewed-by", "Signed-off-by"]);
+    let carol = String::from("Carol <carol@example.com>");
+    assert_eq!(
+        graph.person_related_commits(&carol).unwrap(),
+        vec![commit.clone()]
+    );
+    assert_eq!(
+        graph
+            .query(r#"person -["Signed-off-by"]-> commit"#)
+            .unwrap()
+            .len(),
+        1
+    );
+    let export = graph.export_json();
+    let edge = export
+        .edges
+        .iter()
+        .find(|edge| edge.kind == "Person2Commit")
+        .unwrap();
+    assert_eq!(edge.roles, vec!["Reviewed-by", "Signed-off-by"]);
+    assert!(export.edges.iter().all(|edge| !edge.kind.contains('(')));
+
+    // a role only the other graph knows survives the merge
+    let mut other = RelationGraph::new();
+    other.add_person_node(&carol);
+    other.add_commit_node(&commit);
+    other.add_edge_person2commit(&carol, &commit, "Tested-by");
+    graph.merge(&other);
+    assert_eq!(graph.commit_related_persons(&commit).unwrap().len(), 3);
+}
+
+#[test]
+fn walk_notes() {
+    let mut repo = TestRepo::new("notes");
+    repo.commit(&[("a.rs", "1")], "init", "alice");
+    let commit = repo.commit(&[("a.rs", "2")], "change a", "alice");
+    repo.note(&commit, "refs/notes/ci", "build ok, related to #9");
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    assert!(get_collector().walk(conf.clone()).issues().is_empty());
+
+    conf.notes_refs = vec![String::from("refs/notes/ci")];
+    let graph = get_collector().walk(conf);
+    assert_eq!(graph.commit_related_issues(&commit).unwrap(), vec!["#9"]);
+    assert_eq!(
+        graph.commit_info(&commit).unwrap().notes,
+        vec!["build ok, related to #9"]
+    );
+}
+
+#[test]
+fn walk_notes_repeated_issue() {
+    let mut repo = TestRepo::new("notes-repeat");
+    repo.commit(&[("a.rs", "0\n")], "init", "alice");
+    let commit = repo.commit(&[("a.rs", "1\n2\n")], "fix #9", "alice");
+    repo.note(&commit, "refs/notes/ci", "build ok, related to #9");
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.notes_refs = vec![String::from("refs/notes/ci")];
+    conf.blame = true;
+    let mut observer = RecordObserver::default();
+    let graph = get_collector().walk_with_observer(conf, &mut observer);
+
+    assert_eq!(observer.issues, vec!["#9"]);
+    let owners = graph.file_owners(&String::from("a.rs")).unwrap();
+    assert_eq!(owners.issues[0].lines, 2);
+}
+
+#[test]
+fn walk_churn() {
+    let mut repo = TestRepo::new("churn");
+    repo.commit(&[("a.rs", "1"), ("b.rs", "1")], "init", "alice");
+    repo.commit(&[("b.rs", "1\n2\n3\n4\n5\n6\n7\n8")], "grow b", "alice");
+    repo.commit(&[("b.rs", "8\n7\n6\n5\n4\n3\n2\n1")], "shuffle b", "bob");
+    let old = repo.commit(&[("a.rs", "2")], "change a", "alice");
+    let new = repo.commit(&[("a.rs", "3")], "change a again", "bob");
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    conf.churn = true;
+    let graph = get_collector().walk(conf);
+    assert!(graph.commit_time(&new).unwrap() > graph.commit_time(&old).unwrap());
+
+    // same commit counts, a is fresher
+    let mut model = HotnessModel::default();
+    model.half_life_days = None;
+    let hot = graph.file_hotness(&model);
+    assert_eq!((hot[0].rank, hot[1].rank), (1, 1));
+    model.half_life_days = Some(1.0);
+    assert_eq!(graph.file_hotness(&model)[0].name, "a.rs");
+
+    // b has far more changed lines
+    model.commit_weight = 0.0;
+    model.issue_weight = 0.0;
+    model.churn_weight = 1.0;
+    model.half_life_days = None;
+    assert_eq!(graph.file_hotness(&model)[0].name, "b.rs");
+}
+
+#[test]
+fn walk_rev_graph_diff() {
+    let mut repo = TestRepo::new("rev");
+    repo.commit(&[("a.rs", "1"), ("c.rs", "1")], "init", "alice");
+    let tag = repo.commit(&[("a.rs", "2")], "change a #1", "alice");
+    repo.commit(&[("b.rs", "1")], "add b #2", "bob");
+    repo.remove("c.rs");
+    repo.commit(&[("a.rs", "3")], "change a, remove c", "bob");
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    let newer = get_collector().walk(conf.clone());
+    conf.rev = Some(tag.clone());
+    let older = get_collector().walk(conf);
+    assert_eq!(older.files(), vec!["a.rs"]);
+
+    let mut options = GraphDiffOptions::default();
+    options.min_hotness_delta = 0.5;
+    let diff = older.graph_diff(&newer, &options);
+    // c.rs only shows up in the newer history, by its removal
+    assert_eq!(diff.added_files, vec!["b.rs", "c.rs"]);
+    assert!(diff.removed_files.is_empty());
+    let (added, removed) = rev_added_removed_files(&repo.path_str(), &tag, "HEAD", &[]).unwrap();
+    assert_eq!(added, vec!["b.rs"]);
+    assert_eq!(removed, vec!["c.rs"]);
+    assert_eq!(diff.new_issues, vec!["#2"]);
+    assert_eq!(diff.new_authors.len(), 1);
+    assert!(diff.new_authors[0].starts_with("bob"));
+    let pairs: Vec<&str> = diff
+        .new_author_files
+        .iter()
+        .map(|each| each.file.as_str())
+        .collect();
+    assert_eq!(pairs, vec!["a.rs", "b.rs", "c.rs"]);
+    assert!(diff
+        .hotness_changes
+        .iter()
+        .any(|change| change.file == "b.rs" && change.before_rank.is_none()));
+}
+
+#[test]
+fn worktree_impact() {
+    let mut repo = TestRepo::new("worktree");
+    repo.commit(
+        &[("a.rs", "1"), ("b.rs", "1"), ("c.rs", "1")],
+        "init",
+        "alice",
+    );
+    repo.commit(&[("a.rs", "2"), ("b.rs", "2")], "change a b #1", "alice");
+    repo.commit(&[("a.rs", "3"), ("c.rs", "3")], "change a c #2", "bob");
+    repo.commit(&[("c.rs", "4")], "change c #3", "bob");
+
+    let mut conf = Config::default();
+    conf.repo_path = repo.path_str();
+    let graph = get_collector().walk(conf);
+
+    // uncommitted
+    repo.write("a.rs", "4");
+    repo.write("new.rs", "1");
+    let mut files = worktree_changed_files(&repo.path_str(), &[]).unwrap();
+    files.sort();
+    assert_eq!(files, vec!["a.rs", "new.rs"]);
+
+    let impact = graph.change_impact(&files);
+    assert_eq!(impact.unknown_files, vec!["new.rs"]);
+    assert_eq!(impact.issues.len(), 2);
+    assert_eq!(impact.commits.len(), 2);
+    assert_eq!(impact.authors.len(), 2);
+    assert_eq!(impact.cochanged_files.len(), 2);
+    assert!(impact.cochanged_files.iter().all(|each| each.count == 1));
+    // history untouched
+    assert_eq!(graph.file_size(), 3);
+}
+
+#[test]
+fn diff_changed_files() {
+    let mut repo = TestRepo::new("diff");
+    repo.commit(&[("a.rs", "1"), ("b.rs", "1")], "init", "alice");
+    let base = repo.commit(&[("a.rs", "2")], "change a", "alice");
+    repo.commit(&[("b.rs", "2")], "change b", "bob");
+    let head = repo.commit(&[("c.rs", "2")], "add c", "bob");
+
+    let path = repo.path_str();
+    let mut files = range_changed_files(&path, &format!("{}..{}", base, head), &[]).unwrap();
+    files.sort();
+    assert_eq!(files, vec!["b.rs", "c.rs"]);
+    assert_eq!(
+        range_changed_files(&path, &head, &[]).unwrap(),
+        vec!["c.rs"]
+    );
+    assert!(range_changed_files(&path, "no-such-ref..HEAD", &[]).is_err());
+
+    let patch = "diff --git a/a.rs b/a.rs\n\
+index 0cfbf08..00750ed 100644\n\
+--- a/a.rs\n\
++++ b/a.rs\n\
+@@ -1 +1 @@\n\
+-2\n\
++3\n";
+    assert_eq!(patch_changed_files(patch.as_bytes()).unwrap(), vec!["a.rs"]);
+}
@@ -0,0 +1,144 @@
+#![allow(dead_code)]
+
+use git2::{Repository, Signature, Time};
+use std::fs;
+use std::path::PathBuf;
+
+/// a throwaway git repository for tests
+pub struct TestRepo {
+    pub path: PathBuf,
+    pub repo: Repository,
+    time: i64,
+}
+
+impl TestRepo {
+    pub fn new(name: &str) -> TestRepo {
+        let path = std::env::temp_dir().join(format!("cupido-{}-{}", name, std::process::id()));
+        if path.exists() {
+            fs::remove_dir_all(&path).unwrap();
+        }
+        fs::create_dir_all(&path).unwrap();
+        let repo = Repository::init(&path).unwrap();
+        return TestRepo {
+            path,
+            repo,
+            time: 1_700_000_000,
+        };
+    }
+
+    pub fn path_str(&self) -> String {
+        return self.path.to_string_lossy().to_string();
+    }
+
+    pub fn write(&self, file: &str, content: &str) {
+        let full = self.path.join(file);
+        fs::create_dir_all(full.parent().unwrap()).unwrap();
+        fs::write(full, content).unwrap();
+    }
+
+    /// write files and commit them, returns the commit id
+    pub fn commit(&mut self, files: &[(&str, &str)], message: &str, author: &str) -> String {
+        let parents = match self.repo.head() {
+            Ok(head) => vec![head.peel_to_commit().unwrap().id().to_string()],
+            Err(_) => vec![],
+        };
+        let parents: Vec<&str> = parents.iter().map(|each| each.as_str()).collect();
+        return self.commit_to(Some("HEAD"), &parents, files, message, author);
+    }
+
+    pub fn remove(&self, file: &str) {
+        fs::remove_file(self.path.join(file)).unwrap();
+    }
+
+    /// commit on a side branch, HEAD stays where it is.
+    /// `files` must hold the whole changed content on top of `parent`.
+    pub fn branch_commit(
+        &mut self,
+        parent: &str,
+        files: &[(&str, &str)],
+        message: &str,
+        author: &str,
+    ) -> String {
+        return self.commit_to(None, &[parent], files, message, author);
+    }
+
+    /// merge commit of HEAD and `other`, with the merged content in `files`
+    pub fn merge(
+        &mut self,
+        other: &str,
+        files: &[(&str, &str)],
+        message: &str,
+        author: &str,
+    ) -> String {
+        let head = self
+            .repo
+            .head()
+            .unwrap()
+            .peel_to_commit()
+            .unwrap()
+            .id()
+            .to_string();
+        return self.commit_to(Some("HEAD"), &[&head, other], files, message, author);
+    }
+
+    fn commit_to(
+        &mut self,
+        update_ref: Option<&str>,
+        parents: &[&str],
+        files: &[(&str, &str)],
+        message: &str,
+        author: &str,
+    ) -> String {
+        for (file, content) in files {
+            self.write(file, content);
+        }
+        // one day per commit
+        self.time += 86400;
+        let sig = Signature::new(
+            author,
+            &format!("{}@example.com", author),
+            &Time::new(self.time, 0),
+        )
+        .unwrap();
+
+        let mut index = self.repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        // deleted files
+        index.update_all(["*"].iter(), None).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = self.repo.find_tree(tree_id).unwrap();
+
+        let parents: Vec<_> = parents
+            .iter()
+            .map(|each| {
+                let oid = git2::Oid::from_str(each).unwrap();
+                return self.repo.find_commit(oid).unwrap();
+            })
+            .collect();
+        let parent_refs: Vec<_> = parents.iter().collect();
+        let oid = self
+            .repo
+            .commit(update_ref, &sig, &sig, message, &tree, &parent_refs)
+            .unwrap();
+        return oid.to_string();
+    }
+}
+
+impl TestRepo {
+    pub fn note(&self, commit: &str, notes_ref: &str, message: &str) {
+        let sig = Signature::now("ci", "ci@example.com").unwrap();
+        let oid = git2::Oid::from_str(commit).unwrap();
+        self.repo
+            .note(&sig, &sig, Some(notes_ref), oid, message, false)
+            .unwrap();
+    }
+}
+
+impl Drop for TestRepo {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -1,5 +1,9 @@
 use cupido::collector::config::Collect;
 use cupido::collector::config::{get_collector, Config};
+use cupido::relation::graph::{
+    BusFactorOptions, CentralityMetric, CentralityOptions, CommunityOptions, ExpertModel,
+    ExpertTarget, HotnessModel, NodeKind, PrunePolicy, RelationGraph, SubgraphOptions,
+};
 
 #[test]
 fn graph_query() {
@@ -79,3 +83,609 @@ fn graph_ext() {
     let ranks = graph.file_hot_ranks();
     assert_eq!(ranks.len(), graph.file_size());
 }
+
+/// c1: a b #1 alice, c2: a b #1 bob, c3: a c #2 alice, c4: d #3 bob
+fn sample_graph() -> RelationGraph {
+    return graph_of(&[
+        ("c1", vec!["a", "b"], "#1", "alice"),
+        ("c2", vec!["a", "b"], "#1", "bob"),
+        ("c3", vec!["a", "c"], "#2", "alice"),
+        ("c4", vec!["d"], "#3", "bob"),
+    ]);
+}
+
+fn graph_of(commits: &[(&str, Vec<&str>, &str, &str)]) -> RelationGraph {
+    let mut graph = RelationGraph::new();
+    for (commit, files, issue, author) in commits {
+        let commit = String::from(*commit);
+        let issue = String::from(*issue);
+        let author = String::from(*author);
+        graph.add_commit_node(&commit);
+        graph.add_issue_node(&issue);
+        graph.add_author_node(&author);
+        graph.add_edge_commit2issue(&commit, &issue);
+        graph.add_edge_author2commit(&author, &commit);
+        for file in files {
+            let file = String::from(*file);
+            graph.add_file_node(&file);
+            graph.add_edge_file2commit(&file, &commit);
+            graph.add_edge_file2issue(&file, &issue);
+        }
+    }
+    return graph;
+}
+
+#[test]
+fn graph_coupling() {
+    let mut graph = sample_graph();
+    // a formatting commit touching a, b and 99 more files
+    let format = String::from("c5");
+    graph.add_commit_node(&format);
+    for idx in 0..101 {
+        let file = match idx {
+            0 => String::from("a"),
+            1 => String::from("b"),
+            _ => format!("f{}", idx),
+        };
+        graph.add_file_node(&file);
+        graph.add_edge_file2commit(&file, &format);
+    }
+
+    let coupled = graph.file_coupled_files(&String::from("a"), 1).unwrap();
+    assert_eq!(coupled.len(), 2);
+    assert_eq!(coupled[0].coupled, "b");
+    assert_eq!(coupled[0].support, 2);
+    assert!((coupled[0].confidence - 2.0 / 4.0).abs() < 1e-9);
+    assert!((coupled[0].reverse_confidence - 2.0 / 3.0).abs() < 1e-9);
+    assert!((coupled[0].lift - 5.0 / 6.0).abs() < 1e-9);
+    assert_eq!(
+        graph
+            .file_coupled_files(&String::from("a"), 2)
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(graph.file_coupled_files(&String::from("x"), 1).is_err());
+
+    let report = graph.coupling_report(1);
+    assert_eq!(report.len(), 2);
+    assert_eq!(
+        (report[0].file.as_str(), report[0].coupled.as_str()),
+        ("a", "b")
+    );
+    assert_eq!(report[0].support, coupled[0].support);
+}
+
+#[test]
+fn graph_traverse() {
+    let graph = sample_graph();
+    let a = String::from("a");
+
+    // files sharing issues with a
+    let files = graph
+        .traverse(NodeKind::File, &a, &[NodeKind::Issue, NodeKind::File])
+        .unwrap();
+    assert_eq!(files, vec!["b", "c"]);
+
+    // authors of commits touching a, scored by path count
+    let authors = graph
+        .traverse_scored(NodeKind::File, &a, &[NodeKind::Commit, NodeKind::Author])
+        .unwrap();
+    assert_eq!(authors.len(), 2);
+    assert_eq!((authors[0].name.as_str(), authors[0].count), ("alice", 2));
+
+    assert!(graph
+        .traverse(NodeKind::Issue, &a, &[NodeKind::File])
+        .is_err());
+    assert_eq!("Issue".parse::<NodeKind>(), Ok(NodeKind::Issue));
+}
+
+#[test]
+fn graph_shortest_path() {
+    let graph = sample_graph();
+    let a = String::from("a");
+
+    // a -> c1 / c3 -> alice
+    let path = graph
+        .shortest_path(
+            NodeKind::File,
+            &a,
+            NodeKind::Author,
+            &String::from("alice"),
+            4,
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(path.nodes.len(), 3);
+    assert_eq!(path.edges, vec!["File2Commit", "Author2Commit"]);
+    assert_eq!(path.nodes[0].name, "a");
+    assert_eq!(path.nodes[1].kind, NodeKind::Commit);
+    assert_eq!(path.nodes[2].name, "alice");
+
+    // d only meets a through bob's other commits
+    let d = String::from("d");
+    assert!(graph
+        .shortest_path(NodeKind::File, &a, NodeKind::File, &d, 2)
+        .unwrap()
+        .is_none());
+    let path = graph
+        .shortest_path(NodeKind::File, &a, NodeKind::File, &d, 4)
+        .unwrap()
+        .unwrap();
+    assert_eq!(path.edges.len(), 4);
+
+    assert!(graph
+        .shortest_path(NodeKind::File, &a, NodeKind::File, &String::from("x"), 4)
+        .is_err());
+}
+
+#[test]
+fn graph_query_lang() {
+    let graph = sample_graph();
+
+    let authors = graph
+        .query(r#"file[name="a"] -> commit -> author"#)
+        .unwrap();
+    assert_eq!(authors.len(), 2);
+    assert_eq!((authors[0].name.as_str(), authors[0].count), ("alice", 2));
+
+    // typed edge only: File2Commit, not File2Issue
+    let commits = graph
+        .query(r#"file[name~"a"] -[File2Commit]-> commit"#)
+        .unwrap();
+    assert_eq!(commits.len(), 3);
+    assert_eq!(
+        graph.query("file -[Commit2Issue]-> issue").unwrap().len(),
+        0
+    );
+
+    let issues = graph
+        .query(r#"commit[name!="c4"] -> issue | order name desc | limit 1"#)
+        .unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].name, "#2");
+
+    let error = graph.query("file[size=1]").unwrap_err();
+    assert_eq!(error.position, 5);
+    assert!(graph.query("file -> nothing").is_err());
+    assert!(graph.query("file | limit x").is_err());
+}
+
+#[test]
+fn graph_hotness() {
+    let mut graph = sample_graph();
+    let model = HotnessModel::default();
+
+    // undated: commits + issues, c and d tie
+    let hot = graph.file_hotness(&model);
+    let ranks: Vec<(&str, usize)> = hot.iter().map(|h| (h.name.as_str(), h.rank)).collect();
+    assert_eq!(ranks, vec![("a", 1), ("b", 2), ("c", 3), ("d", 3)]);
+    assert_eq!(hot[0].score, 5.0);
+
+    let dirs = graph.dir_hotness(&model, 1);
+    assert_eq!(dirs.len(), 1);
+    assert_eq!((dirs[0].name.as_str(), dirs[0].score), (".", 12.0));
+
+    // one half life later, only c4 is fresh
+    for commit in ["c1", "c2", "c3"] {
+        graph.set_commit_time(&String::from(commit), 0);
+    }
+    graph.set_commit_time(&String::from("c4"), 180 * 86400);
+    let hot = graph.file_hotness(&model);
+    let names: Vec<&str> = hot.iter().map(|h| h.name.as_str()).collect();
+    assert_eq!(names, vec!["a", "d", "b", "c"]);
+    assert!((hot[0].score - 2.5).abs() < 1e-9);
+    assert_eq!(hot[1].score, 2.0);
+}
+
+#[test]
+fn graph_hot_index() {
+    let mut graph = sample_graph();
+    let d = String::from("d");
+
+    let top = graph.hot_files(2);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[0].name, "a");
+    assert_eq!(graph.file_hot(&d).unwrap().rank, 3);
+    assert!(graph.file_hot(&String::from("x")).is_none());
+    // 5 edges for a, 3 for b, 2 for c and d
+    let d_rank = graph.file_hot_rank(&d);
+    assert_eq!(d_rank, 1);
+    assert_eq!(graph.file_hot_rank(&String::from("c")), 1);
+    assert_eq!(graph.file_hot_rank(&String::from("a")), 4);
+
+    // mutations drop the cached metrics
+    for commit in ["c5", "c6", "c7", "c8"] {
+        let commit = String::from(commit);
+        graph.add_commit_node(&commit);
+        graph.add_edge_file2commit(&d, &commit);
+    }
+    assert_eq!(graph.hot_files(1)[0].name, "d");
+    assert_eq!(graph.file_hot(&d).unwrap().rank, 1);
+    assert!(graph.file_hot_rank(&d) > d_rank);
+    assert_eq!(graph.file_hot_rank(&d), 4);
+    assert_eq!(graph.file_hot_ranks().len(), 4);
+}
+
+#[test]
+fn graph_bus_factor() {
+    let mut graph = sample_graph();
+    let a = String::from("a");
+
+    let mut options = BusFactorOptions::default();
+    let bus = graph.file_bus_factor(&a, &options).unwrap();
+    assert_eq!(bus.commits, 3);
+    assert_eq!(bus.bus_factor, 1);
+    assert_eq!(bus.main_author.as_deref(), Some("alice"));
+    assert!((bus.main_share - 2.0 / 3.0).abs() < 1e-9);
+    assert!(bus.inactive_authors.is_empty());
+
+    options.coverage = 0.9;
+    assert_eq!(graph.file_bus_factor(&a, &options).unwrap().bus_factor, 2);
+    assert!(graph.file_bus_factor(&String::from("x"), &options).is_err());
+    assert_eq!(graph.dir_bus_factor(".", &options).unwrap().commits, 4);
+    assert!(graph.dir_bus_factor("src", &options).is_err());
+
+    // alice stopped a year ago, bob is still around
+    for commit in ["c1", "c2", "c3"] {
+        graph.set_commit_time(&String::from(commit), 0);
+    }
+    graph.set_commit_time(&String::from("c4"), 365 * 86400);
+    let report = graph.bus_factor_report(&BusFactorOptions::default());
+    assert_eq!(report.files.len(), 4);
+    assert_eq!(report.dirs.len(), 1);
+    let orphaned: Vec<&str> = report
+        .orphaned_files
+        .iter()
+        .map(|each| each.name.as_str())
+        .collect();
+    assert_eq!(orphaned, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn graph_experts() {
+    let mut graph = sample_graph();
+    let model = ExpertModel::default();
+
+    let experts = graph
+        .experts_for(&ExpertTarget::File(String::from("a")), &model)
+        .unwrap();
+    assert_eq!(experts.len(), 2);
+    assert_eq!(
+        (experts[0].author.as_str(), experts[0].commits),
+        ("alice", 2)
+    );
+    assert_eq!(experts[0].evidence.len(), 2);
+    assert!(experts[0].explanation.starts_with("2 commits"));
+
+    let experts = graph
+        .experts_for(&ExpertTarget::Issue(String::from("#3")), &model)
+        .unwrap();
+    assert_eq!(experts[0].author, "bob");
+    assert_eq!(
+        graph
+            .experts_for(&ExpertTarget::Dir(String::from(".")), &model)
+            .unwrap()
+            .len(),
+        2
+    );
+    assert!(graph
+        .experts_for(&ExpertTarget::Dir(String::from("src")), &model)
+        .is_err());
+
+    // churn counts by default: bob's one large commit beats alice's two
+    graph.add_file_churn(&String::from("a"), &String::from("c2"), 200);
+    let experts = graph
+        .experts_for(&ExpertTarget::File(String::from("a")), &model)
+        .unwrap();
+    assert_eq!(experts[0].author, "bob");
+    assert_eq!(experts[0].churn, 200);
+    let mut no_churn = ExpertModel::default();
+    no_churn.churn_weight = 0.0;
+    let experts = graph
+        .experts_for(&ExpertTarget::File(String::from("a")), &no_churn)
+        .unwrap();
+    assert_eq!(experts[0].author, "alice");
+
+    // recency: bob's recent commit beats alice's old ones without churn too
+    for commit in ["c1", "c3"] {
+        graph.set_commit_time(&String::from(commit), 0);
+    }
+    graph.set_commit_time(&String::from("c2"), 365 * 86400);
+    let experts = graph
+        .experts_for(&ExpertTarget::File(String::from("a")), &no_churn)
+        .unwrap();
+    assert_eq!(experts[0].author, "bob");
+    assert_eq!(experts[0].last_time, Some(365 * 86400));
+}
+
+#[test]
+fn graph_author_relations() {
+    let graph = sample_graph();
+    let alice = String::from("alice");
+
+    let files = graph.author_related_files(&alice).unwrap();
+    let files: Vec<(&str, usize)> = files.iter().map(|f| (f.name.as_str(), f.count)).collect();
+    assert_eq!(files, vec![("a", 2), ("b", 1), ("c", 1)]);
+
+    let issues = graph.author_related_issues(&String::from("bob")).unwrap();
+    let issues: Vec<(&str, usize)> = issues.iter().map(|i| (i.name.as_str(), i.count)).collect();
+    assert_eq!(issues, vec![("#1", 1), ("#3", 1)]);
+
+    let authors = graph.file_related_authors(&String::from("b")).unwrap();
+    assert_eq!(authors.len(), 2);
+    let authors = graph.issue_related_authors(&String::from("#2")).unwrap();
+    assert_eq!((authors[0].name.as_str(), authors[0].count), ("alice", 1));
+
+    assert!(graph.author_related_files(&String::from("carol")).is_err());
+    assert!(graph.issue_related_authors(&String::from("#9")).is_err());
+}
+
+#[test]
+fn graph_communities() {
+    let graph = sample_graph();
+
+    let communities = graph.communities(&CommunityOptions::default());
+    assert_eq!(communities.len(), 1);
+    let community = &communities[0];
+    assert_eq!(community.files, vec!["a", "b", "c"]);
+    let issues: Vec<&str> = community.issues.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(issues, vec!["#1", "#2"]);
+    assert_eq!(
+        (
+            community.authors[0].name.as_str(),
+            community.authors[0].count
+        ),
+        ("alice", 2)
+    );
+    assert_eq!(community.dir_purity, 1.0);
+
+    // only a and b change together twice
+    let mut options = CommunityOptions::default();
+    options.min_weight = 2;
+    assert_eq!(graph.communities(&options)[0].files, vec!["a", "b"]);
+
+    options = CommunityOptions::default();
+    options.via = NodeKind::Issue;
+    options.min_size = 1;
+    let communities = graph.communities(&options);
+    assert_eq!(communities.len(), 2);
+    assert_eq!(communities[1].files, vec!["d"]);
+}
+
+#[test]
+fn graph_centrality() {
+    let graph = sample_graph();
+
+    // a is the only file linking #1 and #2
+    let mut options = CentralityOptions::default();
+    options.metric = CentralityMetric::Betweenness;
+    let ranking = graph.centrality(&options);
+    assert_eq!(ranking.len(), 4);
+    assert_eq!(ranking[0].name, "a");
+    assert!((ranking[0].score - 4.0 / 15.0).abs() < 1e-9);
+    assert_eq!(ranking[1].score, 0.0);
+    assert_eq!(ranking[3].rank, 2);
+
+    options.metric = CentralityMetric::Degree;
+    let ranking = graph.centrality(&options);
+    assert_eq!(
+        (ranking[0].name.as_str(), ranking[0].score),
+        ("a", 2.0 / 6.0)
+    );
+
+    options.metric = CentralityMetric::PageRank;
+    let ranking = graph.centrality(&options);
+    assert_eq!(ranking[0].name, "a");
+
+    // issues over the full graph
+    options.kind = NodeKind::Issue;
+    options.within = vec![
+        NodeKind::File,
+        NodeKind::Commit,
+        NodeKind::Issue,
+        NodeKind::Author,
+    ];
+    let ranking = graph.centrality(&options);
+    assert_eq!(ranking.len(), 3);
+    assert_eq!(ranking[0].name, "#1");
+
+    // the ranked type joins the subgraph
+    options.kind = NodeKind::Commit;
+    options.within = vec![NodeKind::File];
+    options.metric = CentralityMetric::Degree;
+    let ranking = graph.centrality(&options);
+    assert_eq!(ranking.len(), 4);
+    assert_eq!(ranking[0].rank, 1);
+}
+
+#[test]
+fn graph_subgraph() {
+    let graph = sample_graph();
+    let d = String::from("d");
+
+    // d -> c4, #3 -> bob
+    let mut options = SubgraphOptions::default();
+    let sub = graph.subgraph(NodeKind::File, &d, &options).unwrap();
+    assert_eq!(sub.files(), vec!["d"]);
+    assert_eq!(sub.commits(), vec!["c4"]);
+    assert_eq!(sub.issues(), vec!["#3"]);
+    assert_eq!(sub.authors(), vec!["bob"]);
+    assert_eq!(
+        sub.commit_related_issues(&String::from("c4")).unwrap(),
+        vec!["#3"]
+    );
+
+    let export = sub.export_json();
+    assert_eq!(export.nodes.len(), 4);
+    assert_eq!(export.edges.len(), 4);
+    assert!(sub.to_dot().contains("shape = box"));
+
+    // files and issues only, capped
+    options.kinds = vec![NodeKind::File, NodeKind::Issue];
+    options.hops = 4;
+    let sub = graph
+        .subgraph(NodeKind::File, &String::from("b"), &options)
+        .unwrap();
+    let mut files = sub.files();
+    files.sort();
+    assert_eq!(files, vec!["a", "b", "c"]);
+    assert!(sub.commits().is_empty());
+    options.max_nodes = 2;
+    let sub = graph
+        .subgraph(NodeKind::File, &String::from("b"), &options)
+        .unwrap();
+    assert_eq!(sub.export_json().nodes.len(), 2);
+
+    assert!(graph.subgraph(NodeKind::Issue, &d, &options).is_err());
+}
+
+#[test]
+fn graph_remove() {
+    let mut graph = sample_graph();
+    let a = String::from("a");
+    let c3 = String::from("c3");
+
+    // c3 was the only commit linking a and c to #2
+    assert!(graph.remove_node(NodeKind::Commit, &c3));
+    assert!(!graph.remove_node(NodeKind::Commit, &c3));
+    assert_eq!(graph.file_related_issues(&a).unwrap(), vec!["#1"]);
+    assert!(graph
+        .file_related_issues(&String::from("c"))
+        .unwrap()
+        .is_empty());
+    assert_eq!(graph.commits().len(), 3);
+
+    // every remaining name still points at its own node
+    for commit in graph.commits() {
+        for file in graph.commit_related_files(&commit).unwrap() {
+            assert!(graph.file_related_commits(&file).unwrap().contains(&commit));
+        }
+    }
+    assert_eq!(graph.hot_files(1)[0].name, "a");
+
+    assert!(graph.remove_edge(NodeKind::File, &a, NodeKind::Commit, &String::from("c1")));
+    assert_eq!(graph.file_related_commits(&a).unwrap(), vec!["c2"]);
+    assert!(graph.remove_node(NodeKind::File, &a));
+    assert!(graph.file_related_commits(&a).is_err());
+    assert_eq!(
+        graph.commit_related_files(&String::from("c2")).unwrap(),
+        vec!["b"]
+    );
+}
+
+#[test]
+fn graph_prune() {
+    let mut graph = sample_graph();
+    for (commit, time) in [("c1", 0), ("c2", 0), ("c3", 100), ("c4", 100)] {
+        graph.set_commit_time(&String::from(commit), time);
+    }
+    let symbol = String::from("b::parse");
+    graph.add_symbol_node(&symbol);
+    graph.add_edge_file2symbol(&String::from("b"), &symbol);
+    graph.add_edge_symbol2commit(&symbol, &String::from("c1"));
+
+    // b, #1 and the symbol only hung on c1 and c2
+    let policy = PrunePolicy {
+        before: Some(50),
+        orphans: true,
+        ..Default::default()
+    };
+    let report = graph.prune(&policy).unwrap();
+    assert_eq!((report.commits, report.authors, report.orphans), (2, 0, 3));
+    assert!(graph.symbols().is_empty());
+    let mut files = graph.files();
+    files.sort();
+    assert_eq!(files, vec!["a", "c", "d"]);
+    assert_eq!(graph.issues().len(), 2);
+
+    let policy = PrunePolicy {
+        author_regex: Some(String::from("^bob$")),
+        orphans: true,
+        ..Default::default()
+    };
+    let report = graph.prune(&policy).unwrap();
+    assert_eq!((report.commits, report.authors, report.orphans), (1, 1, 2));
+    assert_eq!(graph.commits(), vec!["c3"]);
+    assert_eq!(graph.authors(), vec!["alice"]);
+
+    let policy = PrunePolicy {
+        max_commit_files: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(graph.prune(&policy).unwrap().commits, 1);
+
+    let policy = PrunePolicy {
+        author_regex: Some(String::from("(")),
+        ..Default::default()
+    };
+    assert!(graph.prune(&policy).is_err());
+}
+
+#[test]
+fn graph_merge() {
+    let sample = sample_graph();
+    let mut graph = graph_of(&[
+        ("c1", vec!["a", "b"], "#1", "alice"),
+        ("c2", vec!["a", "b"], "#1", "bob"),
+    ]);
+    let other = graph_of(&[
+        ("c2", vec!["a", "b"], "#1", "bob"),
+        ("c3", vec!["a", "c"], "#2", "alice"),
+        ("c4", vec!["d"], "#3", "bob"),
+    ]);
+    let c2 = String::from("c2");
+    graph.set_commit_time(&c2, 10);
+
+    let report = graph.merge(&other);
+    assert_eq!(report.added_nodes, 6);
+    assert_eq!(report.added_edges, 10);
+    assert!(report.conflicts.is_empty());
+    assert_eq!(
+        serde_json::to_string(&graph.size()).unwrap(),
+        serde_json::to_string(&sample.size()).unwrap()
+    );
+    let a = String::from("a");
+    let mut issues = graph.file_related_issues(&a).unwrap();
+    issues.sort();
+    assert_eq!(issues, vec!["#1", "#2"]);
+    assert_eq!(graph.file_hot(&a).unwrap().rank, 1);
+    assert_eq!(graph.commit_time(&c2), Some(10));
+
+    // merging again adds nothing, differing commit data is reported
+    let mut other = other;
+    other.set_commit_time(&c2, 20);
+    other.set_commit_time(&String::from("c3"), 30);
+    let report = graph.merge(&other);
+    assert_eq!((report.added_nodes, report.added_edges), (0, 0));
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(report.conflicts[0].field, "time");
+    assert_eq!(
+        (
+            report.conflicts[0].ours.as_str(),
+            report.conflicts[0].theirs.as_str()
+        ),
+        ("10", "20")
+    );
+    assert_eq!(graph.commit_time(&c2), Some(10));
+    assert_eq!(graph.commit_time(&String::from("c3")), Some(30));
+}
+
+#[test]
+fn graph_dir_reviewers() {
+    let mut graph = graph_of(&[
+        ("c1", vec!["src/a.rs", "src/b.rs"], "#1", "alice"),
+        ("c2", vec!["src_old/a.rs"], "#2", "bob"),
+    ]);
+    let carol = String::from("carol");
+    graph.add_person_node(&carol);
+    for commit in ["c1", "c2"] {
+        graph.add_edge_person2commit(&carol, &String::from(commit), "Reviewed-by");
+    }
+
+    // one commit for two files, none from the sibling directory
+    let reviewers = graph.dir_related_reviewers(&String::from("src"), None);
+    assert_eq!(reviewers.get(&carol), Some(&1));
+    let reviewers = graph.dir_related_reviewers(&String::from("."), Some("reviewed-by"));
+    assert_eq!(reviewers.get(&carol), Some(&2));
+}